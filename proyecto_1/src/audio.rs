@@ -0,0 +1,450 @@
+use rodio::{Decoder, OutputStream, OutputStreamHandle, Sink, Source};
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::BufReader;
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/// Efectos de sonido que se cargan al iniciar
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum SoundId {
+    Pickup,
+    Shot,
+    Door,
+    EnemyAlert,
+    EnemyAttack,
+    /// Zumbido en bucle de los objetos por recoger
+    Hum,
+}
+
+impl SoundId {
+    pub const ALL: [SoundId; 6] = [
+        SoundId::Pickup, SoundId::Shot, SoundId::Door,
+        SoundId::EnemyAlert, SoundId::EnemyAttack, SoundId::Hum,
+    ];
+
+    pub fn path(self) -> &'static str {
+        match self {
+            SoundId::Pickup => "assets/collect.wav",
+            SoundId::Shot => "assets/disparo.wav",
+            SoundId::Door => "assets/puerta.wav",
+            SoundId::EnemyAlert => "assets/alerta.wav",
+            SoundId::EnemyAttack => "assets/golpe.wav",
+            SoundId::Hum => "assets/zumbido.wav",
+        }
+    }
+}
+
+/// Distancia (unidades del mundo) a la que un sonido deja de oírse
+pub const HEARING_DISTANCE: f32 = 900.0;
+/// Volumen que queda cuando hay una pared entre el jugador y la fuente
+pub const OCCLUSION: f32 = 0.35;
+
+/// Cómo llega un sonido al jugador: volumen (0..1) y paneo (-1 izquierda, 1 derecha)
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Spatial {
+    pub volume: f32,
+    pub pan: f32,
+}
+
+impl Spatial {
+    /// Sonido sin posición: a todo volumen y centrado
+    pub const CENTER: Spatial = Spatial { volume: 1.0, pan: 0.0 };
+
+    /// Sonido en (x, y) escuchado desde (lx, ly) mirando hacia `la`.
+    /// El volumen cae con el cuadrado de la distancia relativa y el paneo
+    /// sigue el seno del ángulo respecto a la mirada (+ es a la derecha,
+    /// igual que al girar con `rotate_right`).
+    pub fn at(lx: f32, ly: f32, la: f32, x: f32, y: f32, occluded: bool) -> Spatial {
+        let (dx, dy) = (x - lx, y - ly);
+        let distance = (dx * dx + dy * dy).sqrt();
+        let mut volume = (1.0 - distance / HEARING_DISTANCE).max(0.0).powi(2);
+        if occluded { volume *= OCCLUSION; }
+        let pan = if distance < 1.0 { 0.0 } else { (dy.atan2(dx) - la).sin() };
+        Spatial { volume, pan }
+    }
+
+    /// Ganancia de cada canal con paneo de potencia constante
+    fn gains(&self) -> (f32, f32) {
+        let angle = (self.pan.clamp(-1.0, 1.0) + 1.0) * std::f32::consts::FRAC_PI_4;
+        (angle.cos() * self.volume, angle.sin() * self.volume)
+    }
+}
+
+/// Volúmenes que se pueden ajustar por separado
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Channel {
+    Master,
+    Effects,
+    Music,
+}
+
+/// Máximo de efectos sonando a la vez; al pasarse se corta el más viejo
+pub const MAX_VOICES: usize = 8;
+/// Segundos que dura el fundido entre dos temas
+pub const CROSSFADE: f32 = 1.5;
+
+/// Sonido ya decodificado en memoria
+#[derive(Clone)]
+pub struct Clip {
+    channels: u16,
+    sample_rate: u32,
+    samples: Arc<Vec<i16>>,
+}
+
+impl Clip {
+    pub fn load(path: &str) -> Option<Self> {
+        let decoder = open(path)?;
+        let (channels, sample_rate) = (decoder.channels(), decoder.sample_rate());
+        let samples: Vec<i16> = decoder.collect();
+        Some(Clip { channels, sample_rate, samples: Arc::new(samples) })
+    }
+
+    /// Duración en segundos
+    pub fn duration(&self) -> f32 {
+        self.samples.len() as f32 / (self.channels as f32 * self.sample_rate as f32)
+    }
+
+    /// Fuente que lee las muestras compartidas, sin copiarlas
+    fn source(&self) -> ClipSource {
+        ClipSource { clip: self.clone(), next: 0 }
+    }
+}
+
+/// Reproducción de un `Clip` desde la muestra `next`
+#[derive(Clone)]
+struct ClipSource {
+    clip: Clip,
+    next: usize,
+}
+
+impl Iterator for ClipSource {
+    type Item = i16;
+
+    fn next(&mut self) -> Option<i16> {
+        let sample = self.clip.samples.get(self.next).copied()?;
+        self.next += 1;
+        Some(sample)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let left = self.clip.samples.len() - self.next;
+        (left, Some(left))
+    }
+}
+
+impl Source for ClipSource {
+    fn current_frame_len(&self) -> Option<usize> {
+        None // un solo tramo con los mismos canales y frecuencia hasta el final
+    }
+
+    fn channels(&self) -> u16 {
+        self.clip.channels
+    }
+
+    fn sample_rate(&self) -> u32 {
+        self.clip.sample_rate
+    }
+
+    fn total_duration(&self) -> Option<Duration> {
+        Some(Duration::from_secs_f32(self.clip.duration()))
+    }
+}
+
+/// Ganancias izquierda/derecha que se pueden cambiar mientras suena
+#[derive(Default)]
+struct Gains {
+    left: AtomicU32,
+    right: AtomicU32,
+}
+
+impl Gains {
+    fn set(&self, spatial: Spatial) {
+        let (left, right) = spatial.gains();
+        self.left.store(left.to_bits(), Ordering::Relaxed);
+        self.right.store(right.to_bits(), Ordering::Relaxed);
+    }
+}
+
+/// Pasa una fuente a mono y la reparte en estéreo según `Gains`
+struct Panned<S> {
+    input: S,
+    gains: Arc<Gains>,
+    right: Option<i16>, // muestra derecha pendiente del cuadro actual
+}
+
+impl<S: Source<Item = i16>> Iterator for Panned<S> {
+    type Item = i16;
+
+    fn next(&mut self) -> Option<i16> {
+        if let Some(right) = self.right.take() {
+            return Some(right);
+        }
+        let channels = self.input.channels().max(1);
+        let mut sum = 0.0;
+        for _ in 0..channels {
+            sum += self.input.next()? as f32;
+        }
+        let mono = sum / channels as f32;
+        let left = f32::from_bits(self.gains.left.load(Ordering::Relaxed));
+        let right = f32::from_bits(self.gains.right.load(Ordering::Relaxed));
+        self.right = Some((mono * right) as i16);
+        Some((mono * left) as i16)
+    }
+}
+
+impl<S: Source<Item = i16>> Source for Panned<S> {
+    fn current_frame_len(&self) -> Option<usize> {
+        None
+    }
+
+    fn channels(&self) -> u16 {
+        2
+    }
+
+    fn sample_rate(&self) -> u32 {
+        self.input.sample_rate()
+    }
+
+    fn total_duration(&self) -> Option<Duration> {
+        self.input.total_duration()
+    }
+}
+
+fn open(path: &str) -> Option<Decoder<BufReader<File>>> {
+    let file = File::open(path).map_err(|e| eprintln!("No se pudo abrir {}: {}", path, e)).ok()?;
+    Decoder::new(BufReader::new(file)).map_err(|e| eprintln!("No se pudo decodificar {}: {}", path, e)).ok()
+}
+
+/// Un tema de música sonando y cuánto lleva de fundido
+struct Track {
+    sink: Sink,
+    fade: f32, // 0.0 en silencio, 1.0 a todo volumen
+    ends_at: Option<Instant>, // para empezar el siguiente de la lista antes de que termine
+}
+
+/// Todo el audio del juego: un solo stream de salida abierto durante toda
+/// la partida, efectos decodificados de antemano y la música de fondo, que
+/// pasa de un tema a otro con fundidos. `update` avanza los fundidos.
+/// Sin dispositivo de audio funciona igual pero en silencio.
+pub struct AudioManager {
+    // El stream debe vivir mientras suene algo; None es el backend nulo
+    output: Option<(OutputStream, OutputStreamHandle)>,
+    clips: HashMap<SoundId, Clip>,
+    voices: Vec<Sink>,
+    /// Sonidos en bucle con posición, uno por tipo
+    loops: HashMap<SoundId, (Sink, Arc<Gains>)>,
+    music: Option<Track>,
+    fading: Vec<Track>, // temas que se están apagando
+    playlist: Vec<String>,
+    next_track: usize,
+    last_update: Instant,
+    master: f32,
+    effects: f32,
+    music_volume: f32,
+    duck: f32, // atenuación temporal de la música (pausa)
+}
+
+impl AudioManager {
+    /// Abre el dispositivo por defecto y carga los efectos; si no hay
+    /// dispositivo queda como `null`
+    pub fn new() -> Self {
+        let output = match OutputStream::try_default() {
+            Ok(output) => output,
+            Err(e) => {
+                eprintln!("Sin audio: {}", e);
+                return Self::null();
+            }
+        };
+        let clips = SoundId::ALL.iter()
+            .filter_map(|&id| Clip::load(id.path()).map(|clip| (id, clip)))
+            .collect();
+        AudioManager { output: Some(output), clips, ..Self::null() }
+    }
+
+    /// Backend nulo: no abre ningún dispositivo ni carga nada
+    pub fn null() -> Self {
+        AudioManager {
+            output: None,
+            clips: HashMap::new(),
+            voices: Vec::new(),
+            loops: HashMap::new(),
+            music: None,
+            fading: Vec::new(),
+            playlist: Vec::new(),
+            next_track: 0,
+            last_update: Instant::now(),
+            master: 1.0,
+            effects: 1.0,
+            music_volume: 1.0,
+            duck: 1.0,
+        }
+    }
+
+    pub fn is_null(&self) -> bool {
+        self.output.is_none()
+    }
+
+    pub fn play(&mut self, id: SoundId) {
+        self.play_at(id, Spatial::CENTER);
+    }
+
+    /// Efecto con volumen y paneo; no suena si quedó inaudible
+    pub fn play_at(&mut self, id: SoundId, spatial: Spatial) {
+        let Some((_, handle)) = &self.output else { return };
+        let Some(clip) = self.clips.get(&id) else { return };
+        if spatial.volume <= 0.01 { return; }
+        self.voices.retain(|voice| !voice.empty());
+        if self.voices.len() >= MAX_VOICES {
+            self.voices.remove(0).stop();
+        }
+        let Ok(voice) = Sink::try_new(handle) else { return };
+        voice.set_volume(self.master * self.effects);
+        if spatial == Spatial::CENTER {
+            voice.append(clip.source());
+        } else {
+            let gains = Arc::new(Gains::default());
+            gains.set(spatial);
+            voice.append(Panned { input: clip.source(), gains, right: None });
+        }
+        self.voices.push(voice);
+    }
+
+    /// Mantiene sonando `id` en bucle desde donde indica `spatial`, o lo
+    /// corta con None. Se llama en cada paso para seguir al jugador.
+    pub fn set_loop(&mut self, id: SoundId, spatial: Option<Spatial>) {
+        let Some(spatial) = spatial else {
+            self.loops.remove(&id);
+            return;
+        };
+        if let Some((_, gains)) = self.loops.get(&id) {
+            gains.set(spatial);
+            return;
+        }
+        let Some((_, handle)) = &self.output else { return };
+        let Some(clip) = self.clips.get(&id) else { return };
+        let Ok(sink) = Sink::try_new(handle) else { return };
+        let gains = Arc::new(Gains::default());
+        gains.set(spatial);
+        sink.set_volume(self.master * self.effects);
+        sink.append(Panned { input: clip.source().repeat_infinite(), gains: gains.clone(), right: None });
+        self.loops.insert(id, (sink, gains));
+    }
+
+    /// Toca los temas en orden y vuelve a empezar; un solo tema se repite.
+    /// Si la lista es otra, la música actual se funde con la nueva.
+    pub fn play_music(&mut self, tracks: &[String]) {
+        if self.playlist == tracks { return; }
+        self.playlist = tracks.to_vec();
+        self.next_track = 0;
+        self.fade_out_current();
+        self.start_next_track();
+    }
+
+    /// Apaga la música con un fundido
+    pub fn stop_music(&mut self) {
+        self.playlist.clear();
+        self.fade_out_current();
+    }
+
+    /// Lista de temas que está sonando
+    pub fn playlist(&self) -> &[String] {
+        &self.playlist
+    }
+
+    fn fade_out_current(&mut self) {
+        if let Some(track) = self.music.take() {
+            self.fading.push(track);
+        }
+    }
+
+    fn start_next_track(&mut self) {
+        let Some((_, handle)) = &self.output else { return };
+        // Un tema que no abre se salta; si no abre ninguno la música calla
+        let Some(source) = (0..self.playlist.len()).find_map(|_| {
+            let path = &self.playlist[self.next_track];
+            self.next_track = (self.next_track + 1) % self.playlist.len();
+            open(path)
+        }) else { return };
+        let Ok(sink) = Sink::try_new(handle) else { return };
+        sink.set_volume(0.0);
+        let ends_at = if self.playlist.len() == 1 {
+            sink.append(source.repeat_infinite());
+            None
+        } else {
+            let duration = source.total_duration();
+            sink.append(source);
+            duration.map(|d| Instant::now() + d)
+        };
+        self.music = Some(Track { sink, fade: 0.0, ends_at });
+    }
+
+    /// Avanza los fundidos y pasa al siguiente tema de la lista; va una vez por cuadro
+    pub fn update(&mut self) {
+        let now = Instant::now();
+        let step = now.duration_since(self.last_update).as_secs_f32() / CROSSFADE;
+        self.last_update = now;
+
+        if self.playlist.len() > 1
+            && let Some(track) = &self.music
+        {
+            // Sin duración conocida se espera a que termine
+            let ending = track.ends_at.is_some_and(|end| now + Duration::from_secs_f32(CROSSFADE) >= end);
+            if ending || track.sink.empty() {
+                self.fade_out_current();
+                self.start_next_track();
+            }
+        }
+
+        if let Some(track) = &mut self.music {
+            track.fade = (track.fade + step).min(1.0);
+        }
+        for track in &mut self.fading {
+            track.fade = (track.fade - step).max(0.0);
+        }
+        self.fading.retain(|track| track.fade > 0.0);
+        self.update_music_volume();
+    }
+
+    pub fn volume(&self, channel: Channel) -> f32 {
+        match channel {
+            Channel::Master => self.master,
+            Channel::Effects => self.effects,
+            Channel::Music => self.music_volume,
+        }
+    }
+
+    /// Cambia un volumen (0.0 a 1.0); los efectos que ya suenan no cambian
+    pub fn set_volume(&mut self, channel: Channel, volume: f32) {
+        let volume = volume.clamp(0.0, 1.0);
+        match channel {
+            Channel::Master => self.master = volume,
+            Channel::Effects => self.effects = volume,
+            Channel::Music => self.music_volume = volume,
+        }
+        self.update_music_volume();
+        for (sink, _) in self.loops.values() {
+            sink.set_volume(self.master * self.effects);
+        }
+    }
+
+    /// Baja la música a `level` de su volumen sin tocar el ajuste del jugador
+    pub fn duck_music(&mut self, level: f32) {
+        self.duck = level.clamp(0.0, 1.0);
+        self.update_music_volume();
+    }
+
+    fn update_music_volume(&self) {
+        let volume = self.master * self.music_volume * self.duck;
+        for track in self.music.iter().chain(&self.fading) {
+            track.sink.set_volume(track.fade * volume);
+        }
+    }
+}
+
+impl Default for AudioManager {
+    fn default() -> Self {
+        Self::new()
+    }
+}
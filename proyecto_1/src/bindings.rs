@@ -0,0 +1,194 @@
+use crate::map::MapError;
+use crate::platform::{Input, Key};
+use crate::settings::short_float;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+
+/// Lo que el jugador puede hacer en la partida, sin importar con qué tecla
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Action {
+    MoveForward,
+    MoveBackward,
+    StrafeLeft,
+    StrafeRight,
+    TurnLeft,
+    TurnRight,
+    Use,
+    Run,
+    Fire,
+    Map,
+}
+
+impl Action {
+    pub const ALL: [Action; 10] = [
+        Action::MoveForward, Action::MoveBackward,
+        Action::StrafeLeft, Action::StrafeRight,
+        Action::TurnLeft, Action::TurnRight,
+        Action::Use, Action::Run, Action::Fire, Action::Map,
+    ];
+
+    /// Nombre en el archivo de controles
+    pub fn name(self) -> &'static str {
+        match self {
+            Action::MoveForward => "move_forward",
+            Action::MoveBackward => "move_backward",
+            Action::StrafeLeft => "strafe_left",
+            Action::StrafeRight => "strafe_right",
+            Action::TurnLeft => "turn_left",
+            Action::TurnRight => "turn_right",
+            Action::Use => "use",
+            Action::Run => "run",
+            Action::Fire => "fire",
+            Action::Map => "map",
+        }
+    }
+
+    /// Texto para la pantalla de ajustes
+    pub fn label(self) -> &'static str {
+        match self {
+            Action::MoveForward => "avanzar",
+            Action::MoveBackward => "retroceder",
+            Action::StrafeLeft => "paso a la izquierda",
+            Action::StrafeRight => "paso a la derecha",
+            Action::TurnLeft => "girar a la izquierda",
+            Action::TurnRight => "girar a la derecha",
+            Action::Use => "usar / recoger",
+            Action::Run => "correr",
+            Action::Fire => "disparar",
+            Action::Map => "minimapa",
+        }
+    }
+
+    fn bit(self) -> u32 {
+        1 << self as u32
+    }
+}
+
+/// Acciones de un paso de simulación: lo que lee `World` y lo que guarda
+/// una repetición. `turn` es el giro del mouse en radianes.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Actions {
+    pub down: u32,
+    pub pressed: u32,
+    pub turn: f32,
+}
+
+impl Actions {
+    pub fn is_down(&self, action: Action) -> bool {
+        self.down & action.bit() != 0
+    }
+
+    pub fn is_pressed(&self, action: Action) -> bool {
+        self.pressed & action.bit() != 0
+    }
+
+    pub fn hold(&mut self, action: Action) {
+        self.down |= action.bit();
+    }
+
+    pub fn press(&mut self, action: Action) {
+        self.down |= action.bit();
+        self.pressed |= action.bit();
+    }
+}
+
+/// Teclas asignadas a cada acción y ajustes del mouse
+#[derive(Clone, Debug)]
+pub struct Bindings {
+    keys: HashMap<Action, Vec<Key>>,
+    /// Girar con el mouse. Apagado por defecto: la ventana no captura el
+    /// puntero, así que se gira con la posición absoluta del cursor y el giro
+    /// se corta al llegar al borde
+    pub mouse_look: bool,
+    pub mouse_sensitivity: f32, // radianes por píxel
+}
+
+/// Controles tal como se escriben en el archivo de ajustes: cada acción
+/// (por su `name`) con la lista de nombres de teclas
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Controls {
+    pub mouse_look: bool,
+    #[serde(serialize_with = "short_float")]
+    pub mouse_sensitivity: f32,
+    pub keys: BTreeMap<String, Vec<String>>,
+}
+
+impl Default for Controls {
+    fn default() -> Self {
+        Bindings::default().to_controls()
+    }
+}
+
+impl Default for Bindings {
+    fn default() -> Self {
+        let keys = [
+            (Action::MoveForward, vec![Key::W, Key::Up]),
+            (Action::MoveBackward, vec![Key::S, Key::Down]),
+            (Action::StrafeLeft, vec![Key::A]),
+            (Action::StrafeRight, vec![Key::D]),
+            (Action::TurnLeft, vec![Key::Left]),
+            (Action::TurnRight, vec![Key::Right]),
+            (Action::Use, vec![Key::E]),
+            (Action::Run, vec![Key::LeftShift]),
+            (Action::Fire, vec![Key::Space]),
+            (Action::Map, vec![Key::M]),
+        ];
+        Bindings { keys: keys.into_iter().collect(), mouse_look: false, mouse_sensitivity: 0.004 }
+    }
+}
+
+impl Bindings {
+    /// Lee una sección de controles en TOML; `path` solo se usa en los errores
+    pub fn parse(text: &str, path: &str) -> Result<Self, MapError> {
+        let error = |message: String| MapError::Parse { path: path.to_string(), message };
+        let controls: Controls = toml::from_str(text).map_err(|e| error(e.to_string()))?;
+        Self::from_controls(&controls).map_err(error)
+    }
+
+    /// Las acciones que no aparecen conservan sus teclas por defecto
+    pub fn from_controls(controls: &Controls) -> Result<Self, String> {
+        let mut bindings = Bindings {
+            mouse_look: controls.mouse_look,
+            mouse_sensitivity: controls.mouse_sensitivity,
+            ..Bindings::default()
+        };
+        for (name, names) in &controls.keys {
+            let action = Action::ALL.into_iter().find(|a| a.name() == name)
+                .ok_or_else(|| format!("acción desconocida '{}'", name))?;
+            let keys = names.iter()
+                .map(|k| Key::from_name(k).ok_or_else(|| format!("tecla desconocida '{}' en '{}'", k, name)))
+                .collect::<Result<Vec<_>, _>>()?;
+            bindings.keys.insert(action, keys);
+        }
+        Ok(bindings)
+    }
+
+    pub fn to_controls(&self) -> Controls {
+        let keys = Action::ALL.iter()
+            .map(|&a| (a.name().to_string(), self.keys(a).iter().map(|k| k.name().to_string()).collect()))
+            .collect();
+        Controls { mouse_look: self.mouse_look, mouse_sensitivity: self.mouse_sensitivity, keys }
+    }
+
+    pub fn keys(&self, action: Action) -> &[Key] {
+        self.keys.get(&action).map_or(&[], Vec::as_slice)
+    }
+
+    /// Traduce el teclado y el desplazamiento del mouse (en píxeles) a acciones
+    pub fn resolve(&self, input: &Input, mouse_dx: f32) -> Actions {
+        let mut actions = Actions::default();
+        for action in Action::ALL {
+            let keys = self.keys(action);
+            if keys.iter().any(|&k| input.is_pressed(k)) {
+                actions.press(action);
+            } else if keys.iter().any(|&k| input.is_down(k)) {
+                actions.hold(action);
+            }
+        }
+        if self.mouse_look {
+            actions.turn = mouse_dx * self.mouse_sensitivity;
+        }
+        actions
+    }
+}
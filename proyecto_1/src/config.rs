@@ -1,6 +1,14 @@
 // Tamaño de cada celda del mapa en “unidades del mundo”
-pub const BLOCK_SIZE: usize = 100;
+pub const BLOCK_SIZE: usize = 64; // Debe coincidir con raycaster y colisiones
 
-// Velocidades
-pub const MOVE_SPEED: f32 = 5.0;
-pub const ROT_SPEED: f32 = 0.05;
+// Velocidades (por segundo)
+pub const MOVE_SPEED: f32 = 240.0;   // unidades del mundo
+pub const RUN_MULTIPLIER: f32 = 1.8; // factor de correr
+pub const ROT_SPEED: f32 = 3.0;      // radianes
+
+// Simulación a paso fijo
+pub const TICK_RATE: u32 = 60;
+pub const TICK: f32 = 1.0 / TICK_RATE as f32;
+// Tope de tiempo por cuadro: tras una pausa larga (p. ej. arrastrar la
+// ventana) no se intenta recuperar todo de golpe
+pub const MAX_FRAME_TIME: f32 = 0.25;
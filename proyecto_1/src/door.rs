@@ -0,0 +1,156 @@
+use crate::inventory::{Inventory, KeyColor};
+use crate::player::Player;
+use std::collections::HashMap;
+
+/// Fracción de apertura que avanza una puerta por segundo
+const DOOR_SPEED: f32 = 1.5;
+/// Segundos que una puerta queda abierta antes de cerrarse sola
+const DOOR_OPEN_TIME: f32 = 3.0;
+/// Apertura mínima para poder atravesar la puerta
+const PASSABLE_OPENING: f32 = 0.9;
+
+/// 'D' puerta horizontal (se cruza en el eje Y), 'd' puerta vertical (eje X),
+/// 'R'/'G'/'Y' puertas con llave (la orientación se deduce de las paredes vecinas)
+pub fn is_door(c: char) -> bool {
+    c == 'D' || c == 'd' || KeyColor::from_lock_tile(c).is_some()
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DoorState {
+    Closed,
+    Opening,
+    Open,
+    Closing,
+}
+
+pub struct Door {
+    pub open: f32, // 0.0 = cerrada, 1.0 = abierta por completo
+    pub state: DoorState,
+    pub horizontal: bool,
+    pub lock: Option<KeyColor>, // llave necesaria para abrirla
+    timer: f32, // tiempo que lleva abierta
+}
+
+/// Estado de todas las puertas del laberinto, indexado por celda (columna, fila)
+pub struct Doors {
+    doors: HashMap<(usize, usize), Door>,
+}
+
+impl Doors {
+    pub fn from_maze(maze: &[Vec<char>]) -> Self {
+        let mut doors = HashMap::new();
+        for (j, row) in maze.iter().enumerate() {
+            for (i, &cell) in row.iter().enumerate() {
+                if is_door(cell) {
+                    let horizontal = match cell {
+                        'D' => true,
+                        'd' => false,
+                        // Con llave: horizontal si tiene paredes a izquierda y derecha
+                        _ => {
+                            let solid = |x: Option<usize>| {
+                                x.and_then(|x| row.get(x)).is_none_or(|&c| c != ' ' && !is_door(c))
+                            };
+                            solid(i.checked_sub(1)) && solid(Some(i + 1))
+                        }
+                    };
+                    doors.insert((i, j), Door {
+                        open: 0.0,
+                        state: DoorState::Closed,
+                        horizontal,
+                        lock: KeyColor::from_lock_tile(cell),
+                        timer: 0.0,
+                    });
+                }
+            }
+        }
+        Doors { doors }
+    }
+
+    /// Apertura de la puerta en la celda (0.0 si no hay puerta)
+    pub fn openness(&self, i: usize, j: usize) -> f32 {
+        self.doors.get(&(i, j)).map_or(0.0, |d| d.open)
+    }
+
+    pub fn is_passable(&self, i: usize, j: usize) -> bool {
+        self.openness(i, j) >= PASSABLE_OPENING
+    }
+
+    /// ¿El panel de la puerta corre a lo largo del eje X?
+    pub fn is_horizontal(&self, i: usize, j: usize) -> bool {
+        self.doors.get(&(i, j)).is_none_or(|d| d.horizontal)
+    }
+
+    pub fn state(&self, i: usize, j: usize) -> Option<DoorState> {
+        self.doors.get(&(i, j)).map(|d| d.state)
+    }
+
+    /// Llave que exige la puerta de la celda, si está cerrada con llave
+    pub fn required_key(&self, i: usize, j: usize) -> Option<KeyColor> {
+        self.doors.get(&(i, j)).and_then(|d| d.lock)
+    }
+
+    /// Abrir (o volver a abrir) la puerta de la celda. Devuelve false si no hay
+    /// puerta o si tiene llave y el inventario no la contiene.
+    pub fn activate(&mut self, i: usize, j: usize, inventory: &Inventory) -> bool {
+        match self.doors.get_mut(&(i, j)) {
+            Some(door) => {
+                if let Some(color) = door.lock
+                    && !inventory.has_key(color)
+                {
+                    return false;
+                }
+                match door.state {
+                    DoorState::Closed | DoorState::Closing => door.state = DoorState::Opening,
+                    DoorState::Open => door.timer = 0.0,
+                    DoorState::Opening => {}
+                }
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Avanza la animación. Una puerta no se cierra mientras el jugador esté en su celda.
+    /// Devuelve las celdas de las puertas que empezaron a cerrarse solas.
+    pub fn update(&mut self, dt: f32, player: &Player, block_size: usize) -> Vec<(usize, usize)> {
+        let mut closing = Vec::new();
+        for (&(i, j), door) in self.doors.iter_mut() {
+            match door.state {
+                DoorState::Opening => {
+                    door.open = (door.open + DOOR_SPEED * dt).min(1.0);
+                    if door.open >= 1.0 {
+                        door.state = DoorState::Open;
+                        door.timer = 0.0;
+                    }
+                }
+                DoorState::Open => {
+                    door.timer += dt;
+                    if door.timer >= DOOR_OPEN_TIME && !occupies(player, i, j, block_size) {
+                        door.state = DoorState::Closing;
+                        closing.push((i, j));
+                    }
+                }
+                DoorState::Closing => {
+                    if occupies(player, i, j, block_size) {
+                        door.state = DoorState::Opening;
+                        continue;
+                    }
+                    door.open = (door.open - DOOR_SPEED * dt).max(0.0);
+                    if door.open <= 0.0 {
+                        door.state = DoorState::Closed;
+                    }
+                }
+                DoorState::Closed => {}
+            }
+        }
+        closing
+    }
+}
+
+/// ¿El círculo del jugador toca la celda (i, j)?
+fn occupies(player: &Player, i: usize, j: usize, block_size: usize) -> bool {
+    let bs = block_size as f32;
+    let (x0, y0) = (i as f32 * bs, j as f32 * bs);
+    player.pos.x + player.radius > x0 && player.pos.x - player.radius < x0 + bs &&
+    player.pos.y + player.radius > y0 && player.pos.y - player.radius < y0 + bs
+}
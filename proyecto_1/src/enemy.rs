@@ -0,0 +1,236 @@
+use crate::door::Doors;
+use crate::pathfind::{astar, walkable};
+use crate::player::{collides, Player, Position};
+use crate::raycaster::{cast_ray_from, Sprite};
+
+/// Velocidad de los enemigos (unidades del mundo por segundo)
+pub const ENEMY_SPEED: f32 = 90.0;
+/// Hasta dónde ven al jugador (unidades del mundo)
+pub const SIGHT_RANGE: f32 = 512.0;
+/// Distancia a la que dejan de acercarse y atacan
+pub const ATTACK_RANGE: f32 = 48.0;
+/// Segundos entre ataques
+pub const ATTACK_COOLDOWN: f32 = 1.0;
+/// Daño de cada ataque
+const ENEMY_DAMAGE: i32 = 10;
+/// Segundos que siguen persiguiendo tras perder de vista al jugador
+const MEMORY_TIME: f32 = 3.0;
+/// Cada cuánto recalculan el camino mientras persiguen
+const REPATH_TIME: f32 = 0.5;
+/// Pausa en cada extremo de la ronda
+const IDLE_TIME: f32 = 1.5;
+/// Celdas que recorre como máximo una ronda de patrulla
+const PATROL_LENGTH: usize = 4;
+
+/// Caracteres del mapa que marcan un enemigo: 'e' patrulla, 'E' monta guardia
+pub fn is_enemy(c: char) -> bool {
+    c == 'e' || c == 'E'
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum EnemyState {
+    Idle,
+    Patrol,
+    Chase,
+    Attack,
+}
+
+pub struct Enemy {
+    pub pos: Position,
+    pub kind: char, // carácter del mapa, también su textura
+    pub state: EnemyState,
+    pub radius: f32,
+    pub health: i32,
+    patrol: Vec<(usize, usize)>, // celdas entre las que hace la ronda
+    next_waypoint: usize,
+    path: Vec<(usize, usize)>,   // celdas pendientes hasta el destino
+    timer: f32,                  // pausa en Idle, recálculo en Chase, recarga en Attack
+    memory: f32,                 // tiempo que le queda persiguiendo sin ver al jugador
+}
+
+impl Enemy {
+    /// Crea un enemigo en el centro de la celda (i, j). Los 'e' arman su
+    /// ronda por el pasillo más largo que sale de esa celda.
+    pub fn new(i: usize, j: usize, kind: char, maze: &[Vec<char>], doors: &Doors, block: usize) -> Self {
+        let bs = block as f32;
+        let patrol = if kind == 'e' { patrol_route(maze, doors, i, j) } else { Vec::new() };
+        Enemy {
+            pos: Position { x: (i as f32 + 0.5) * bs, y: (j as f32 + 0.5) * bs },
+            kind,
+            state: EnemyState::Idle,
+            radius: 12.0,
+            health: if kind == 'E' { 100 } else { 50 }, // los guardias aguantan más
+            patrol,
+            next_waypoint: 0,
+            path: Vec::new(),
+            timer: IDLE_TIME,
+            memory: 0.0,
+        }
+    }
+
+    pub fn sprite(&self) -> Sprite {
+        Sprite { x: self.pos.x, y: self.pos.y, texture: self.kind }
+    }
+
+    pub fn is_alive(&self) -> bool {
+        self.health > 0
+    }
+
+    /// Daño de cada ataque (los guardias pegan el doble)
+    pub fn damage(&self) -> i32 {
+        if self.kind == 'E' { ENEMY_DAMAGE * 2 } else { ENEMY_DAMAGE }
+    }
+
+    /// Recibe un disparo: pierde vida y sale a buscar al jugador
+    pub fn take_damage(&mut self, amount: i32) {
+        self.health -= amount;
+        self.memory = MEMORY_TIME;
+        if !matches!(self.state, EnemyState::Chase | EnemyState::Attack) {
+            self.state = EnemyState::Chase;
+            self.path.clear();
+            self.timer = 0.0;
+        }
+    }
+
+    /// ¿Hay línea de visión hasta el jugador? Usa el mismo rayo que el render,
+    /// así que las paredes y puertas cerradas tapan la vista.
+    pub fn sees(&self, player: &Player, maze: &[Vec<char>], doors: &Doors, block: usize) -> bool {
+        let dx = player.pos.x - self.pos.x;
+        let dy = player.pos.y - self.pos.y;
+        let dist = (dx*dx + dy*dy).sqrt();
+        if dist > SIGHT_RANGE { return false; }
+        let hit = cast_ray_from(maze, doors, self.pos.x, self.pos.y, dy.atan2(dx), block);
+        hit.distance >= dist
+    }
+
+    /// Avanza la máquina de estados. Devuelve true si ataca en este paso.
+    pub fn update(&mut self, dt: f32, player: &Player, maze: &[Vec<char>], doors: &Doors, block: usize) -> bool {
+        let dx = player.pos.x - self.pos.x;
+        let dy = player.pos.y - self.pos.y;
+        let dist = (dx*dx + dy*dy).sqrt();
+
+        if self.sees(player, maze, doors, block) {
+            self.memory = MEMORY_TIME;
+            let next = if dist <= ATTACK_RANGE { EnemyState::Attack } else { EnemyState::Chase };
+            if next != self.state {
+                self.state = next;
+                self.path.clear();
+                self.timer = 0.0;
+            }
+        } else if matches!(self.state, EnemyState::Chase | EnemyState::Attack) {
+            self.memory -= dt;
+            if self.memory <= 0.0 {
+                // Lo perdió: vuelve a su ronda (o a quedarse quieto)
+                self.state = EnemyState::Idle;
+                self.path.clear();
+                self.timer = IDLE_TIME;
+            } else {
+                self.state = EnemyState::Chase;
+            }
+        }
+
+        match self.state {
+            EnemyState::Idle => {
+                self.timer -= dt;
+                if self.timer <= 0.0 && !self.patrol.is_empty() {
+                    self.state = EnemyState::Patrol;
+                }
+                false
+            }
+            EnemyState::Patrol => {
+                if self.path.is_empty() {
+                    let goal = self.patrol[self.next_waypoint];
+                    self.path = astar(maze, doors, self.cell(block), goal).unwrap_or_default();
+                }
+                if self.follow_path(dt, maze, doors, block) {
+                    // Llegó al extremo: pausa y luego al siguiente punto
+                    self.next_waypoint = (self.next_waypoint + 1) % self.patrol.len();
+                    self.state = EnemyState::Idle;
+                    self.timer = IDLE_TIME;
+                }
+                false
+            }
+            EnemyState::Chase => {
+                self.timer -= dt;
+                if self.timer <= 0.0 || self.path.is_empty() {
+                    let bs = block as f32;
+                    let goal = ((player.pos.x / bs) as usize, (player.pos.y / bs) as usize);
+                    self.path = astar(maze, doors, self.cell(block), goal).unwrap_or_default();
+                    self.timer = REPATH_TIME;
+                }
+                self.follow_path(dt, maze, doors, block);
+                false
+            }
+            EnemyState::Attack => {
+                self.timer -= dt;
+                if self.timer <= 0.0 {
+                    self.timer = ATTACK_COOLDOWN;
+                    true
+                } else {
+                    false
+                }
+            }
+        }
+    }
+
+    fn cell(&self, block: usize) -> (usize, usize) {
+        let bs = block as f32;
+        ((self.pos.x / bs) as usize, (self.pos.y / bs) as usize)
+    }
+
+    /// Camina hacia el centro de la siguiente celda del camino.
+    /// Devuelve true cuando ya no quedan celdas.
+    fn follow_path(&mut self, dt: f32, maze: &[Vec<char>], doors: &Doors, block: usize) -> bool {
+        let Some(&(i, j)) = self.path.first() else { return true };
+        let bs = block as f32;
+        let tx = (i as f32 + 0.5) * bs;
+        let ty = (j as f32 + 0.5) * bs;
+        let dx = tx - self.pos.x;
+        let dy = ty - self.pos.y;
+        let dist = (dx*dx + dy*dy).sqrt();
+        let step = ENEMY_SPEED * dt;
+
+        if dist <= step {
+            self.try_move(dx, dy, maze, doors, block);
+            self.path.remove(0);
+        } else {
+            self.try_move(dx / dist * step, dy / dist * step, maze, doors, block);
+        }
+        self.path.is_empty()
+    }
+
+    /// Mismo deslizamiento por ejes que el jugador
+    fn try_move(&mut self, dx: f32, dy: f32, maze: &[Vec<char>], doors: &Doors, block: usize) {
+        let nx = self.pos.x + dx;
+        if !collides(maze, doors, nx, self.pos.y, self.radius, block) {
+            self.pos.x = nx;
+        }
+        let ny = self.pos.y + dy;
+        if !collides(maze, doors, self.pos.x, ny, self.radius, block) {
+            self.pos.y = ny;
+        }
+    }
+}
+
+/// Ronda de ida y vuelta: la celda inicial y el extremo del tramo recto
+/// más largo (hasta `PATROL_LENGTH` celdas) en alguna de las cuatro direcciones
+fn patrol_route(maze: &[Vec<char>], doors: &Doors, i: usize, j: usize) -> Vec<(usize, usize)> {
+    let mut best = (i, j);
+    let mut best_len = 0;
+    for (dx, dy) in [(0i32, -1i32), (1, 0), (0, 1), (-1, 0)] {
+        let (mut x, mut y) = (i, j);
+        let mut len = 0;
+        while len < PATROL_LENGTH {
+            let (nx, ny) = (x as i32 + dx, y as i32 + dy);
+            if nx < 0 || ny < 0 || ny as usize >= maze.len() || nx as usize >= maze[0].len() { break; }
+            if !walkable(maze, doors, nx as usize, ny as usize) { break; }
+            (x, y) = (nx as usize, ny as usize);
+            len += 1;
+        }
+        if len > best_len {
+            best = (x, y);
+            best_len = len;
+        }
+    }
+    if best_len == 0 { Vec::new() } else { vec![best, (i, j)] }
+}
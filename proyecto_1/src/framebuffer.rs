@@ -45,4 +45,27 @@ impl Framebuffer {
             }
         }
     }
-}
\ No newline at end of file
+
+    /// Oscurece todo el cuadro multiplicando cada canal por `factor` (0..1)
+    pub fn dim(&mut self, factor: f32) {
+        let f = (factor.clamp(0.0, 1.0) * 256.0) as u32;
+        for pixel in &mut self.buffer {
+            let r = (((*pixel >> 16) & 0xFF) * f) >> 8;
+            let g = (((*pixel >> 8) & 0xFF) * f) >> 8;
+            let b = ((*pixel & 0xFF) * f) >> 8;
+            *pixel = (r << 16) | (g << 8) | b;
+        }
+    }
+
+    /// Copia el contenido a una imagen RGB (para guardar o comparar sin ventana)
+    pub fn to_image(&self) -> image::RgbImage {
+        image::RgbImage::from_fn(self.width as u32, self.height as u32, |x, y| {
+            let c = self.buffer[y as usize * self.width + x as usize];
+            image::Rgb([(c >> 16) as u8, (c >> 8) as u8, c as u8])
+        })
+    }
+
+    pub fn save_png(&self, path: &str) -> image::ImageResult<()> {
+        self.to_image().save_with_format(path, image::ImageFormat::Png)
+    }
+}
@@ -0,0 +1,648 @@
+use crate::audio::Channel;
+use crate::bindings::{Action, Actions, Bindings};
+use crate::config::{MAX_FRAME_TIME, TICK};
+use crate::framebuffer::Framebuffer;
+use crate::hud::{draw_text, draw_text_centered};
+use crate::level::Level;
+use crate::map::MapError;
+use crate::mapgen;
+use crate::platform::{FixedInput, Input, Key, Platform};
+use crate::replay::Replay;
+use crate::settings::{Settings, Video, FOV_RANGE, SENSITIVITY_RANGE};
+use crate::world::{Outcome, World};
+use image::RgbImage;
+use rusttype::Font;
+use std::path::PathBuf;
+
+/// De dónde sale el nivel en juego, para poder reiniciarlo
+#[derive(Clone, Debug, PartialEq)]
+pub enum LevelSource {
+    File(String),
+    Random(u64), // semilla
+}
+
+impl LevelSource {
+    pub fn load(&self) -> Result<Level, MapError> {
+        match self {
+            LevelSource::File(path) => Level::load(path),
+            LevelSource::Random(seed) => {
+                let opts = mapgen::GenOptions { seed: *seed, ..Default::default() };
+                Level::from_grid(&format!("Aleatorio #{}", seed), mapgen::generate(&opts))
+            }
+        }
+    }
+}
+
+/// Niveles del menú, en orden
+pub const LEVELS: [&str; 3] = [
+    "./levels/nivel1.toml",
+    "./levels/nivel2.toml",
+    "./levels/nivel3.toml",
+];
+
+/// Opciones del menú de pausa, en orden
+pub const PAUSE_OPTIONS: [&str; 4] = ["Continuar", "Reiniciar nivel", "Ajustes", "Salir al menú"];
+/// Volumen de la música mientras el juego está en pausa
+const PAUSED_MUSIC_VOLUME: f32 = 0.25;
+
+/// Volúmenes que se ajustan en la pantalla de ajustes, en orden
+pub const VOLUME_SLIDERS: [(&str, Channel); 3] = [
+    ("Volumen general", Channel::Master),
+    ("Música", Channel::Music),
+    ("Efectos", Channel::Effects),
+];
+/// Cuánto cambia un volumen con cada flecha
+const VOLUME_STEP: f32 = 0.1;
+/// Ajustes de la pantalla de ajustes que no son volúmenes
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum OtherSetting {
+    Fov,
+    MouseSensitivity,
+    Resolution,
+}
+
+/// Filas de la pantalla de ajustes debajo de los volúmenes, en orden
+pub const OTHER_SETTINGS: [(&str, OtherSetting); 3] = [
+    ("Campo de visión", OtherSetting::Fov),
+    ("Sensibilidad del mouse", OtherSetting::MouseSensitivity),
+    ("Resolución", OtherSetting::Resolution),
+];
+const FOV_STEP: f32 = 5.0;
+const SENSITIVITY_STEP: f32 = 0.0005;
+/// Tamaños de ventana que ofrece la pantalla de ajustes; se aplican al reiniciar
+pub const RESOLUTIONS: [(usize, usize); 6] = [
+    (640, 480), (800, 600), (1024, 768), (1280, 720), (1280, 960), (1920, 1080),
+];
+
+/// Música de las pantallas de menú y de la victoria; cada nivel trae la suya
+pub const TITLE_MUSIC: &str = "assets/musica_titulo.wav";
+pub const VICTORY_MUSIC: &str = "assets/musica_victoria.wav";
+
+/// Pantallas del juego. La de arriba de la pila recibe el teclado; las
+/// superpuestas (pausa) se dibujan encima de la que tienen debajo.
+pub enum Scene {
+    Title,
+    LevelSelect,
+    Playing(Box<World>),
+    Paused { selected: usize }, // opción marcada de `PAUSE_OPTIONS`
+    Victory,
+    GameOver,
+    Settings { selected: usize }, // fila marcada: volúmenes y luego `OTHER_SETTINGS`
+}
+
+impl Scene {
+    fn is_overlay(&self) -> bool {
+        matches!(self, Scene::Paused { .. })
+    }
+}
+
+/// Qué hacer con la pila después de un cuadro
+pub enum Transition {
+    Stay,
+    Push(Scene),
+    Pop,
+    /// Vacía la pila hasta el título y apila esta escena encima
+    Reset(Scene),
+    /// Vuelve a cargar el nivel actual desde cero
+    Restart,
+    Quit,
+}
+
+/// Pila de escenas con todo el flujo del juego: título, selección de nivel,
+/// partida, pausa, victoria, derrota y ajustes, en un solo bucle principal.
+pub struct Game {
+    stack: Vec<Scene>,
+    font: Font<'static>,
+    current: Option<LevelSource>,
+    title_image: Option<RgbImage>,
+    victory_image: Option<RgbImage>,
+    fps: f32,
+    /// Tiempo real todavía no simulado
+    accumulator: f32,
+    /// Teclas apretadas en cuadros sin paso de simulación, para no perderlas
+    pending: Input,
+    /// Desplazamiento del mouse (píxeles) todavía no aplicado
+    pending_mouse: f32,
+    last_mouse: Option<f32>,
+    /// Ajustes en uso y el archivo donde se guardan al cambiarlos. `saved`
+    /// es lo que va al archivo: no incluye lo cambiado desde la línea de comandos.
+    settings: Settings,
+    saved: Settings,
+    settings_path: Option<PathBuf>,
+    settings_changed: bool,
+    /// Sale de `settings.controls`
+    bindings: Bindings,
+    /// Temas que se pidieron a la plataforma la última vez
+    music: Vec<String>,
+    /// Si está activo, cada partida que empieza se graba en `recording`
+    record: bool,
+    recording: Option<Replay>,
+    /// Repetición en curso y el próximo paso a usar
+    playback: Option<(Replay, usize)>,
+    /// La última repetición llegó a su fin y el jugador tomó el control
+    playback_finished: bool,
+}
+
+impl Game {
+    /// `width`/`height` son el tamaño del framebuffer, para escalar los fondos una sola vez
+    pub fn new(font: Font<'static>, width: usize, height: usize) -> Self {
+        Game {
+            stack: vec![Scene::Title],
+            font,
+            current: None,
+            title_image: load_background("assets/Radioheadkida.png", width, height),
+            victory_image: load_background("assets/victory.png", width, height),
+            fps: 0.0,
+            accumulator: 0.0,
+            pending: Input::default(),
+            pending_mouse: 0.0,
+            last_mouse: None,
+            settings: Settings::default(),
+            saved: Settings::default(),
+            settings_path: None,
+            settings_changed: false,
+            bindings: Bindings::default(),
+            music: Vec::new(),
+            record: false,
+            recording: None,
+            playback: None,
+            playback_finished: false,
+        }
+    }
+
+    /// La escena activa
+    pub fn scene(&self) -> &Scene {
+        self.stack.last().expect("la pila de escenas nunca queda vacía")
+    }
+
+    /// Partida en curso, aunque esté en pausa
+    pub fn world(&self) -> Option<&World> {
+        self.stack.iter().rev().find_map(|s| match s {
+            Scene::Playing(world) => Some(world.as_ref()),
+            _ => None,
+        })
+    }
+
+    pub fn world_mut(&mut self) -> Option<&mut World> {
+        self.stack.iter_mut().rev().find_map(|s| match s {
+            Scene::Playing(world) => Some(world.as_mut()),
+            _ => None,
+        })
+    }
+
+    /// Arranca una partida con el nivel indicado encima del menú. Los niveles
+    /// aleatorios llevan la semilla en el nombre, que va al título de la ventana.
+    pub fn start(&mut self, source: LevelSource, platform: &mut dyn Platform) -> Result<(), MapError> {
+        let level = source.load()?;
+        platform.set_title(&level.name);
+        if self.record {
+            let mut replay = Replay::new(source.clone());
+            replay.gameplay = self.settings.gameplay;
+            self.recording = Some(replay);
+        }
+        self.playback = None;
+        self.playback_finished = false;
+        self.current = Some(source);
+        let mut world = World::new(level);
+        world.gameplay = self.settings.gameplay;
+        world.player.fov = self.settings.video.fov_radians();
+        self.stack = vec![Scene::Title, Scene::LevelSelect, Scene::Playing(Box::new(world))];
+        Ok(())
+    }
+
+    /// Usa estos ajustes (volúmenes, controles, velocidades y campo de visión).
+    /// Si hay `path`, los cambios de la pantalla de ajustes se guardan ahí.
+    pub fn set_settings(&mut self, settings: Settings, path: Option<PathBuf>, platform: &mut dyn Platform) {
+        for (_, channel) in VOLUME_SLIDERS {
+            platform.set_volume(channel, settings.audio.get(channel));
+        }
+        self.bindings = settings.bindings();
+        if let Some(world) = self.world_mut() {
+            world.gameplay = settings.gameplay;
+            world.player.fov = settings.video.fov_radians();
+        }
+        self.saved = settings.clone();
+        self.settings = settings;
+        self.settings_path = path;
+        self.settings_changed = false;
+    }
+
+    /// Cambia la ventana y el campo de visión solo para esta sesión;
+    /// no se escribe en el archivo de ajustes
+    pub fn set_video(&mut self, video: Video) {
+        self.settings.video = video;
+        if let Some(world) = self.world_mut() {
+            world.player.fov = video.fov_radians();
+        }
+    }
+
+    pub fn settings(&self) -> &Settings {
+        &self.settings
+    }
+
+    /// Graba las partidas que empiecen de aquí en adelante
+    pub fn record(&mut self) {
+        self.record = true;
+    }
+
+    /// Grabación de la última partida iniciada
+    pub fn recording(&self) -> Option<&Replay> {
+        self.recording.as_ref()
+    }
+
+    /// Arranca el nivel de la repetición y la reproduce en lugar del teclado.
+    /// ESC sigue pausando; al acabarse las entradas, el control vuelve al jugador.
+    pub fn play(&mut self, replay: Replay, platform: &mut dyn Platform) -> Result<(), MapError> {
+        self.start(replay.level.clone(), platform)?;
+        if let Some(world) = self.world_mut() {
+            world.gameplay = replay.gameplay;
+        }
+        self.playback = Some((replay, 0));
+        Ok(())
+    }
+
+    /// ¿Se acabaron los pasos de la repetición antes de terminar la partida?
+    pub fn playback_finished(&self) -> bool {
+        self.playback_finished
+    }
+
+    /// Un cuadro completo: lee el teclado, avanza la escena activa y dibuja.
+    /// Devuelve false cuando el jugador quiere salir.
+    pub fn frame(&mut self, dt: f32, platform: &mut dyn Platform, framebuffer: &mut Framebuffer) -> bool {
+        if dt > 0.0 { self.fps = 1.0 / dt; }
+
+        // La lógica avanza a pasos fijos de `TICK`; el dibujo, una vez por cuadro
+        let input = Input::read(platform);
+        self.pending.down = input.down;
+        self.pending.pressed |= input.pressed;
+        let mouse = platform.mouse_x();
+        if let (Some(x), Some(last)) = (mouse, self.last_mouse) {
+            self.pending_mouse += x - last;
+        }
+        self.last_mouse = mouse;
+        self.accumulator = (self.accumulator + dt).min(MAX_FRAME_TIME);
+        while self.accumulator >= TICK {
+            self.accumulator -= TICK;
+            if !self.tick(platform) { return false; }
+        }
+        self.render(framebuffer);
+        true
+    }
+
+    /// Un paso de simulación con el teclado acumulado; las teclas apretadas
+    /// cuentan solo en el primer paso
+    fn tick(&mut self, platform: &mut dyn Platform) -> bool {
+        let input = self.pending;
+        self.pending.pressed = 0;
+        let mut actions = self.bindings.resolve(&input, std::mem::take(&mut self.pending_mouse));
+        // Solo se graban los pasos que avanzan la partida (no el ESC que la pausa)
+        if matches!(self.scene(), Scene::Playing(_)) && !input.is_pressed(Key::Escape) {
+            if let Some((replay, next)) = &mut self.playback {
+                match replay.ticks.get(*next) {
+                    Some(&recorded) => {
+                        actions = recorded;
+                        *next += 1;
+                    }
+                    None => {
+                        self.playback = None;
+                        self.playback_finished = true;
+                        if let Some(world) = self.world_mut() {
+                            world.show_message("Fin de la repetición");
+                        }
+                    }
+                }
+            } else if let Some(replay) = &mut self.recording {
+                replay.ticks.push(actions);
+            }
+        }
+        let transition = self.update(TICK, &actions, &mut FixedInput { platform, input });
+        match transition {
+            Transition::Stay => {}
+            Transition::Push(scene) => self.stack.push(scene),
+            Transition::Pop => {
+                if self.stack.len() > 1 { self.stack.pop(); }
+            }
+            Transition::Reset(scene) => {
+                self.stack = vec![Scene::Title];
+                if !matches!(scene, Scene::Title) { self.stack.push(scene); }
+            }
+            Transition::Restart => {
+                if let Some(source) = self.current.clone()
+                    && let Err(e) = self.start(source, platform)
+                {
+                    eprintln!("No se pudo reiniciar el nivel: {}", e);
+                    self.stack = vec![Scene::Title, Scene::LevelSelect];
+                }
+            }
+            Transition::Quit => return false,
+        }
+        // Fuera de la partida no deben quedar sonidos en bucle
+        if !matches!(self.scene(), Scene::Playing(_)) {
+            World::stop_loops(platform);
+        }
+        self.update_music(platform);
+        true
+    }
+
+    /// Temas que corresponden a la escena de arriba: los del nivel mientras
+    /// haya partida (también en pausa), y los propios del menú y la victoria
+    fn scene_music(&self) -> Vec<String> {
+        match self.scene() {
+            Scene::Victory => vec![VICTORY_MUSIC.to_string()],
+            Scene::GameOver => Vec::new(),
+            _ => match self.world() {
+                Some(world) => world.level.music.clone(),
+                None => vec![TITLE_MUSIC.to_string()],
+            },
+        }
+    }
+
+    fn update_music(&mut self, platform: &mut dyn Platform) {
+        let music = self.scene_music();
+        if music == self.music { return; }
+        if music.is_empty() {
+            platform.stop_music();
+        } else {
+            platform.play_music(&music);
+        }
+        self.music = music;
+    }
+
+    fn update(&mut self, dt: f32, actions: &Actions, platform: &mut dyn Platform) -> Transition {
+        let pressed = |key| platform.is_key_pressed(key);
+        match self.stack.last_mut().expect("la pila de escenas nunca queda vacía") {
+            Scene::Title => {
+                if pressed(Key::Enter) { Transition::Push(Scene::LevelSelect) }
+                else if pressed(Key::Escape) { Transition::Quit }
+                else { Transition::Stay }
+            }
+            Scene::LevelSelect => {
+                let choice = [Key::Key1, Key::Key2, Key::Key3].iter()
+                    .position(|&k| platform.is_key_pressed(k))
+                    .map(|n| LevelSource::File(LEVELS[n].to_string()))
+                    .or_else(|| platform.is_key_pressed(Key::Key4).then(|| {
+                        // Semilla distinta en cada partida
+                        let seed = std::time::SystemTime::now()
+                            .duration_since(std::time::UNIX_EPOCH)
+                            .map_or(0, |d| d.as_secs());
+                        LevelSource::Random(seed)
+                    }));
+                if let Some(source) = choice {
+                    if let Err(e) = self.start(source, platform) {
+                        eprintln!("Error al cargar el nivel: {}", e);
+                    }
+                    Transition::Stay
+                } else if platform.is_key_pressed(Key::Key5) {
+                    Transition::Push(Scene::Settings { selected: 0 })
+                } else if platform.is_key_pressed(Key::Escape) {
+                    Transition::Pop
+                } else {
+                    Transition::Stay
+                }
+            }
+            Scene::Playing(world) => {
+                if platform.is_key_pressed(Key::Escape) {
+                    platform.duck_music(PAUSED_MUSIC_VOLUME);
+                    return Transition::Push(Scene::Paused { selected: 0 });
+                }
+                match world.update(dt, actions, platform) {
+                    Outcome::Playing => Transition::Stay,
+                    Outcome::Won => Transition::Push(Scene::Victory),
+                    Outcome::Lost => Transition::Push(Scene::GameOver),
+                }
+            }
+            Scene::Paused { selected } => {
+                let n = PAUSE_OPTIONS.len();
+                if pressed(Key::Up) || pressed(Key::W) { *selected = (*selected + n - 1) % n; }
+                if pressed(Key::Down) || pressed(Key::S) { *selected = (*selected + 1) % n; }
+                let choice = if pressed(Key::Escape) { Some(0) } // ESC también continúa
+                    else if pressed(Key::Enter) { Some(*selected) }
+                    else { None };
+                match choice {
+                    Some(0) => {
+                        platform.duck_music(1.0);
+                        Transition::Pop
+                    }
+                    Some(1) => {
+                        platform.duck_music(1.0);
+                        Transition::Restart
+                    }
+                    Some(2) => Transition::Push(Scene::Settings { selected: 0 }),
+                    Some(_) => {
+                        platform.duck_music(1.0);
+                        Transition::Reset(Scene::LevelSelect)
+                    }
+                    None => Transition::Stay,
+                }
+            }
+            Scene::Victory | Scene::GameOver => {
+                if pressed(Key::R) { Transition::Restart }
+                else if pressed(Key::Enter) { Transition::Reset(Scene::LevelSelect) }
+                else if pressed(Key::Escape) { Transition::Reset(Scene::Title) }
+                else { Transition::Stay }
+            }
+            Scene::Settings { selected } => {
+                let n = VOLUME_SLIDERS.len() + OTHER_SETTINGS.len();
+                if pressed(Key::Up) || pressed(Key::W) { *selected = (*selected + n - 1) % n; }
+                if pressed(Key::Down) || pressed(Key::S) { *selected = (*selected + 1) % n; }
+                let change = if pressed(Key::Left) || pressed(Key::A) { -1.0 }
+                    else if pressed(Key::Right) || pressed(Key::D) { 1.0 }
+                    else { 0.0 };
+                let done = pressed(Key::Escape) || pressed(Key::Enter);
+                let row = *selected;
+                if change != 0.0 {
+                    self.change_setting(row, change, platform);
+                }
+                if done {
+                    self.save_settings();
+                    Transition::Pop
+                } else {
+                    Transition::Stay
+                }
+            }
+        }
+    }
+
+    /// Mueve un paso (`dir` = -1 o 1) el ajuste de la fila `row`
+    fn change_setting(&mut self, row: usize, dir: f32, platform: &mut dyn Platform) {
+        let (settings, saved) = (&mut self.settings, &mut self.saved);
+        match row.checked_sub(VOLUME_SLIDERS.len()).map(|n| OTHER_SETTINGS[n].1) {
+            None => {
+                let channel = VOLUME_SLIDERS[row].1;
+                let volume = settings.audio.get_mut(channel);
+                // Redondeo a décimas para no acumular error con tantos pasos
+                *volume = ((*volume + dir * VOLUME_STEP).clamp(0.0, 1.0) * 10.0).round() / 10.0;
+                platform.set_volume(channel, *volume);
+                *saved.audio.get_mut(channel) = *volume;
+            }
+            Some(OtherSetting::Fov) => {
+                // Cada copia avanza desde su propio valor: el archivo no debe
+                // quedarse con el `--fov` de la línea de comandos
+                let step = |fov: f32| (fov + dir * FOV_STEP).clamp(FOV_RANGE.0, FOV_RANGE.1);
+                settings.video.fov = step(settings.video.fov);
+                saved.video.fov = step(saved.video.fov);
+                let fov = settings.video.fov_radians();
+                if let Some(world) = self.world_mut() {
+                    world.player.fov = fov;
+                }
+            }
+            Some(OtherSetting::MouseSensitivity) => {
+                let steps = (settings.controls.mouse_sensitivity / SENSITIVITY_STEP).round() + dir;
+                let sensitivity = (steps * SENSITIVITY_STEP).clamp(SENSITIVITY_RANGE.0, SENSITIVITY_RANGE.1);
+                settings.controls.mouse_sensitivity = sensitivity;
+                saved.controls.mouse_sensitivity = sensitivity;
+                self.bindings.mouse_sensitivity = sensitivity;
+            }
+            Some(OtherSetting::Resolution) => {
+                let video = &mut settings.video;
+                let n = RESOLUTIONS.len();
+                let next = match RESOLUTIONS.iter().position(|&r| r == (video.width, video.height)) {
+                    Some(i) if dir > 0.0 => (i + 1) % n,
+                    Some(i) => (i + n - 1) % n,
+                    None => 0,
+                };
+                (video.width, video.height) = RESOLUTIONS[next];
+                (saved.video.width, saved.video.height) = RESOLUTIONS[next];
+            }
+        }
+        self.settings_changed = true;
+    }
+
+    /// Escribe los ajustes si cambiaron y hay dónde
+    fn save_settings(&mut self) {
+        if !std::mem::take(&mut self.settings_changed) { return; }
+        let Some(path) = &self.settings_path else { return };
+        if let Err(e) = self.saved.save(path) {
+            eprintln!("No se pudo guardar {}: {}", path.display(), e);
+        }
+    }
+
+    fn render(&self, fb: &mut Framebuffer) {
+        // Desde la última escena opaca hacia arriba
+        let base = self.stack.iter().rposition(|s| !s.is_overlay()).unwrap_or(0);
+        for scene in &self.stack[base..] {
+            self.render_scene(scene, fb);
+        }
+    }
+
+    fn render_scene(&self, scene: &Scene, fb: &mut Framebuffer) {
+        // Todo se ubica relativo al tamaño del framebuffer (s = escala respecto a 480 px de alto)
+        let (w, h) = (fb.width, fb.height);
+        let s = h as f32 / 480.0;
+        let y = |offset: f32| (h as f32 / 2.0 + offset * s).max(0.0) as usize;
+        let font = &self.font;
+
+        match scene {
+            Scene::Title => {
+                draw_background(fb, self.title_image.as_ref(), 0x000000);
+                draw_text_centered(fb, font, "atrapa el radio objeto", w / 2, y(-50.0), 0xFFFFFF, 40.0 * s);
+                draw_text_centered(fb, font, "Presiona ENTER para comenzar", w / 2, y(20.0), 0xFFFF00, 24.0 * s);
+            }
+            Scene::LevelSelect => {
+                fb.clear(0x000000);
+                draw_text_centered(fb, font, "Selecciona un nivel:", w / 2, y(-100.0), 0xFFFFFF, 32.0 * s);
+                draw_text_centered(fb, font, "1 - Nivel 1", w / 2, y(-40.0), 0x00FF00, 24.0 * s);
+                draw_text_centered(fb, font, "2 - Nivel 2", w / 2, y(0.0), 0x00FF00, 24.0 * s);
+                draw_text_centered(fb, font, "3 - Nivel 3", w / 2, y(40.0), 0x00FF00, 24.0 * s);
+                draw_text_centered(fb, font, "4 - Nivel aleatorio", w / 2, y(80.0), 0x00FF00, 24.0 * s);
+                draw_text_centered(fb, font, "5 - Ajustes", w / 2, y(120.0), 0xA0A8C0, 24.0 * s);
+            }
+            Scene::Playing(world) => world.render(fb, font, self.fps),
+            Scene::Paused { selected } => {
+                // El cuadro congelado de la partida queda oscurecido detrás
+                fb.dim(0.35);
+                draw_text_centered(fb, font, "PAUSA", w / 2, y(-110.0), 0xFFFFFF, 40.0 * s);
+                for (n, option) in PAUSE_OPTIONS.iter().enumerate() {
+                    let (text, color) = if n == *selected {
+                        (format!("> {} <", option), 0xFFFF00)
+                    } else {
+                        (option.to_string(), 0xA0A8C0)
+                    };
+                    draw_text_centered(fb, font, &text, w / 2, y(-30.0 + n as f32 * 40.0), color, 26.0 * s);
+                }
+            }
+            Scene::Victory => {
+                draw_background(fb, self.victory_image.as_ref(), 0x000000);
+                draw_text_centered(fb, font, "¡FELICIDADES!", w / 2, y(-50.0), 0x00FF00, 40.0 * s);
+                draw_text_centered(fb, font, "Has recolectado todos los objetos", w / 2, y(20.0), 0xFFFFFF, 24.0 * s);
+                draw_text_centered(fb, font, "R reiniciar · ENTER elegir nivel · ESC menú", w / 2, y(70.0), 0xFFFF00, 20.0 * s);
+            }
+            Scene::GameOver => {
+                fb.clear(0x400000);
+                draw_text_centered(fb, font, "HAS MUERTO", w / 2, y(-50.0), 0xFF3030, 40.0 * s);
+                draw_text_centered(fb, font, "Los enemigos te alcanzaron", w / 2, y(20.0), 0xFFFFFF, 24.0 * s);
+                draw_text_centered(fb, font, "R reiniciar · ENTER elegir nivel · ESC menú", w / 2, y(70.0), 0xFFFF00, 20.0 * s);
+            }
+            Scene::Settings { selected } => {
+                fb.clear(0x101018);
+                draw_text_centered(fb, font, "Ajustes", w / 2, y(-200.0), 0xFFFFFF, 32.0 * s);
+
+                // Barras de volumen y, debajo, los demás ajustes con su valor
+                let (label_x, bar_x) = (w / 2 - (190.0 * s) as usize, w / 2 + (10.0 * s) as usize);
+                let (bar_w, bar_h) = ((170.0 * s) as usize, (12.0 * s).max(2.0) as usize);
+                let labels = VOLUME_SLIDERS.iter().map(|(label, _)| *label)
+                    .chain(OTHER_SETTINGS.iter().map(|(label, _)| *label));
+                for (n, label) in labels.enumerate() {
+                    let row = y(-165.0 + n as f32 * 26.0);
+                    let color = if n == *selected { 0xFFFF00 } else { 0xA0A8C0 };
+                    let text = if n == *selected { format!("> {}", label) } else { label.to_string() };
+                    draw_text(fb, font, &text, label_x, row, color, 20.0 * s);
+                    let video = &self.settings.video;
+                    let value = match n.checked_sub(VOLUME_SLIDERS.len()).map(|i| OTHER_SETTINGS[i].1) {
+                        None => {
+                            let bar_y = row + (6.0 * s) as usize;
+                            let volume = self.settings.audio.get(VOLUME_SLIDERS[n].1);
+                            fb.fill_rect(bar_x, bar_y, bar_w, bar_h, 0x303040);
+                            fb.fill_rect(bar_x, bar_y, (bar_w as f32 * volume) as usize, bar_h, color);
+                            continue;
+                        }
+                        Some(OtherSetting::Fov) => format!("{:.0}°", video.fov),
+                        Some(OtherSetting::MouseSensitivity) => format!("{:.4}", self.settings.controls.mouse_sensitivity),
+                        // El tamaño de la ventana solo cambia al volver a abrir el juego
+                        Some(OtherSetting::Resolution) if (video.width, video.height) != (w, h) =>
+                            format!("{}x{} (al reiniciar)", video.width, video.height),
+                        Some(OtherSetting::Resolution) => format!("{}x{}", video.width, video.height),
+                    };
+                    draw_text(fb, font, &value, bar_x, row, color, 20.0 * s);
+                }
+
+                // Teclas actuales según los ajustes
+                for (n, action) in Action::ALL.iter().enumerate() {
+                    let keys: Vec<&str> = self.bindings.keys(*action).iter().map(|k| k.name()).collect();
+                    let line = format!("{}: {}", action.label(), keys.join(" / "));
+                    draw_text_centered(fb, font, &line, w / 2, y(-2.0 + n as f32 * 17.0), 0x8088A0, 15.0 * s);
+                }
+                let mouse = if self.bindings.mouse_look { "mouse: girar" } else { "mouse: desactivado" };
+                draw_text_centered(fb, font, mouse, w / 2, y(168.0), 0x8088A0, 15.0 * s);
+                draw_text_centered(fb, font, "Flechas: elegir y ajustar · ESC para volver", w / 2, y(205.0), 0xFFFF00, 18.0 * s);
+            }
+        }
+    }
+}
+
+fn load_background(path: &str, w: usize, h: usize) -> Option<RgbImage> {
+    match image::open(path) {
+        Ok(img) => Some(img.resize_exact(w as u32, h as u32, image::imageops::FilterType::Triangle).to_rgb8()),
+        Err(e) => {
+            eprintln!("No se pudo cargar {}: {}", path, e);
+            None
+        }
+    }
+}
+
+/// Copia la imagen como fondo (o pinta `fallback` si no hay)
+fn draw_background(fb: &mut Framebuffer, img: Option<&RgbImage>, fallback: u32) {
+    let Some(img) = img else {
+        fb.clear(fallback);
+        return;
+    };
+    for y in 0..fb.height.min(img.height() as usize) {
+        for x in 0..fb.width.min(img.width() as usize) {
+            let px = img.get_pixel(x as u32, y as u32);
+            let r = px[0] as u32;
+            let g = px[1] as u32;
+            let b = px[2] as u32;
+            fb.point(x, y, (r << 16) | (g << 8) | b);
+        }
+    }
+}
@@ -0,0 +1,74 @@
+use crate::bindings::{Action, Actions};
+use crate::config::TICK;
+use crate::door::Doors;
+use crate::enemy::Enemy;
+use crate::framebuffer::Framebuffer;
+use crate::level::Level;
+use crate::platform::NullPlatform;
+use crate::player::Player;
+use crate::raycaster::{render3d, render_minimap, Scene, Sprite};
+use crate::textures::Textures;
+use crate::world::World;
+use rusttype::Font;
+use std::time::{Duration, Instant};
+
+/// Punto de vista para un render sin ventana
+pub struct Camera {
+    pub x: f32, // unidades del mundo
+    pub y: f32,
+    pub a: f32, // radianes
+    pub width: usize,
+    pub height: usize,
+}
+
+/// Dibuja un cuadro del nivel tal como lo vería el jugador desde `camera`,
+/// con las puertas cerradas y los enemigos en su posición inicial
+pub fn render_level(level: &Level, camera: &Camera, block_size: usize) -> Framebuffer {
+    let mut textures = Textures::new();
+    textures.apply_legend(&level.textures);
+    let doors = Doors::from_maze(&level.maze);
+    let sprites: Vec<Sprite> = level.enemies.iter()
+        .map(|&(i, j, kind)| Enemy::new(i, j, kind, &level.maze, &doors, block_size).sprite())
+        .collect();
+    let player = Player::new(camera.x, camera.y, camera.a, std::f32::consts::FRAC_PI_2);
+
+    let mut framebuffer = Framebuffer::new(camera.width, camera.height);
+    let scene = Scene { maze: &level.maze, doors: &doors, surfaces: &level.surfaces, textures: &textures, sprites: &sprites };
+    render3d(&mut framebuffer, &player, &scene, block_size);
+    render_minimap(&mut framebuffer, &player, &level.maze, 10, 10, 4, block_size);
+    framebuffer
+}
+
+/// Tiempos de `benchmark`
+pub struct BenchmarkReport {
+    pub frames: usize,
+    pub total: Duration,
+    pub slowest: Duration,
+}
+
+impl BenchmarkReport {
+    pub fn average(&self) -> Duration {
+        self.total / self.frames.max(1) as u32
+    }
+}
+
+/// Simula y dibuja `frames` cuadros del nivel sin ventana ni audio, con el
+/// jugador girando en el spawn para que la vista recorra todo el entorno
+pub fn benchmark(level: Level, frames: usize, width: usize, height: usize, font: &Font<'_>) -> BenchmarkReport {
+    let mut world = World::new(level);
+    let mut platform = NullPlatform::new();
+    let mut framebuffer = Framebuffer::new(width, height);
+    let mut actions = Actions::default();
+    actions.hold(Action::TurnRight);
+
+    let mut report = BenchmarkReport { frames, total: Duration::ZERO, slowest: Duration::ZERO };
+    for _ in 0..frames {
+        let start = Instant::now();
+        world.update(TICK, &actions, &mut platform);
+        world.render(&mut framebuffer, font, 0.0);
+        let elapsed = start.elapsed();
+        report.total += elapsed;
+        report.slowest = report.slowest.max(elapsed);
+    }
+    report
+}
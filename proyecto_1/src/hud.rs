@@ -0,0 +1,289 @@
+use crate::framebuffer::Framebuffer;
+use crate::player::{Player, MAX_HEALTH};
+use image::RgbaImage;
+use rusttype::{point, Font, Scale};
+use std::fs::File;
+use std::io::Read;
+
+// ============================================================================
+// Texto con rusttype
+// ============================================================================
+
+pub fn load_font(path: &str) -> Font<'static> {
+    let mut data = Vec::new();
+    File::open(path).expect("No se pudo abrir la fuente (fonts/Arial.ttf)")
+        .read_to_end(&mut data)
+        .expect("No se pudo leer la fuente");
+    Font::try_from_vec(data).expect("Fuente inválida o corrupta")
+}
+
+/// Dibuja texto sólido (sin blending con fondo) con color RGB 0xRRGGBB
+pub fn draw_text(fb: &mut Framebuffer, font: &Font<'_>, text: &str, x: usize, y: usize, color: u32, size: f32) {
+    let scale = Scale { x: size, y: size };
+    let v_metrics = font.v_metrics(scale);
+    let mut cursor_x = x as f32;
+    let baseline_y = y as f32 + v_metrics.ascent;
+
+    for ch in text.chars() {
+        let glyph = font.glyph(ch).scaled(scale).positioned(point(cursor_x, baseline_y));
+        if let Some(bb) = glyph.pixel_bounding_box() {
+            glyph.draw(|gx, gy, v| {
+                if v == 0.0 { return; }
+                let px = bb.min.x + gx as i32;
+                let py = bb.min.y + gy as i32;
+                if px >= 0 && py >= 0 && (px as usize) < fb.width && (py as usize) < fb.height {
+                    // Escalamos el color por la cobertura 'v' para antialias
+                    let r = ((color >> 16) & 0xFF) as f32 * v;
+                    let g = ((color >> 8) & 0xFF) as f32 * v;
+                    let b = (color & 0xFF) as f32 * v;
+                    let col = ((r as u32) << 16) | ((g as u32) << 8) | (b as u32);
+                    fb.point(px as usize, py as usize, col);
+                }
+            });
+        }
+        cursor_x += glyph.unpositioned().h_metrics().advance_width;
+    }
+}
+
+/// Ancho en píxeles de un texto, para centrarlo
+pub fn text_width(font: &Font<'_>, text: &str, size: f32) -> usize {
+    let scale = Scale { x: size, y: size };
+    text.chars()
+        .map(|ch| font.glyph(ch).scaled(scale).h_metrics().advance_width)
+        .sum::<f32>() as usize
+}
+
+/// Texto centrado en `cx`
+pub fn draw_text_centered(fb: &mut Framebuffer, font: &Font<'_>, text: &str, cx: usize, y: usize, color: u32, size: f32) {
+    let x = cx.saturating_sub(text_width(font, text, size) / 2);
+    draw_text(fb, font, text, x, y, color, size);
+}
+
+// ============================================================================
+// Barra de estado
+// ============================================================================
+
+const BAR_COLOR: u32 = 0x202838;
+const BORDER_COLOR: u32 = 0x5A6A8A;
+const LABEL_COLOR: u32 = 0xA0A8C0;
+const VALUE_COLOR: u32 = 0xFFFF00;
+/// Segundos que se ve un aviso
+const MESSAGE_TIME: f32 = 2.5;
+
+/// Lo que muestra la barra además del jugador
+pub struct HudInfo<'a> {
+    pub level_name: &'a str,
+    pub total_items: u32,
+}
+
+/// Barra inferior (salud, armadura, munición, cara, llaves, objetos y nivel).
+/// Todas las medidas salen del tamaño del framebuffer.
+pub struct StatusBar {
+    portrait: Option<RgbaImage>,
+    message: Option<(String, f32)>, // aviso y segundos que le quedan
+}
+
+impl Default for StatusBar {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl StatusBar {
+    pub fn new() -> Self {
+        StatusBar { portrait: load_sprite("sprites/cara.png"), message: None }
+    }
+
+    /// Muestra un aviso arriba de la vista durante unos segundos
+    pub fn show(&mut self, text: impl Into<String>) {
+        self.message = Some((text.into(), MESSAGE_TIME));
+    }
+
+    pub fn message(&self) -> Option<&str> {
+        self.message.as_ref().map(|(text, _)| text.as_str())
+    }
+
+    pub fn update(&mut self, dt: f32) {
+        if let Some((_, time)) = &mut self.message {
+            *time -= dt;
+            if *time <= 0.0 {
+                self.message = None;
+            }
+        }
+    }
+
+    /// Alto de la barra para un framebuffer de alto `h`
+    pub fn height(h: usize) -> usize {
+        (h / 7).max(24)
+    }
+
+    pub fn draw(&self, fb: &mut Framebuffer, font: &Font<'_>, player: &Player, info: &HudInfo) {
+        let (w, h) = (fb.width, fb.height);
+        let bar_h = Self::height(h);
+        let top = h - bar_h;
+        fb.fill_rect(0, top, w, bar_h, BAR_COLOR);
+        fb.fill_rect(0, top, w, 2.max(bar_h / 24), BORDER_COLOR);
+
+        let label = bar_h as f32 * 0.22;
+        let value = bar_h as f32 * 0.45;
+        let label_y = top + bar_h / 8;
+        let value_y = top + bar_h * 2 / 5;
+        // Columnas como fracción del ancho: (inicio, fin)
+        let col = |from: f32, to: f32| ((w as f32 * from) as usize, (w as f32 * to) as usize);
+
+        // Nivel y objetos
+        let (x0, x1) = col(0.0, 0.24);
+        draw_text_centered(fb, font, info.level_name, (x0 + x1) / 2, label_y, LABEL_COLOR, label);
+        let items = format!("{}/{}", player.get_collected_items(), info.total_items);
+        draw_text_centered(fb, font, &items, (x0 + x1) / 2, value_y, VALUE_COLOR, value);
+
+        // Salud, en rojo cuando queda poca
+        let (x0, x1) = col(0.24, 0.40);
+        let health_color = if player.health <= MAX_HEALTH / 4 { 0xFF3030 } else { VALUE_COLOR };
+        draw_text_centered(fb, font, "SALUD", (x0 + x1) / 2, label_y, LABEL_COLOR, label);
+        draw_text_centered(fb, font, &format!("{}%", player.health), (x0 + x1) / 2, value_y, health_color, value);
+
+        // Cara en el centro
+        let (x0, x1) = col(0.40, 0.52);
+        let side = bar_h.saturating_sub(bar_h / 6);
+        self.draw_portrait(fb, player, (x0 + x1) / 2 - side / 2, top + (bar_h - side) / 2 + 1, side);
+
+        let (x0, x1) = col(0.52, 0.68);
+        draw_text_centered(fb, font, "ARMADURA", (x0 + x1) / 2, label_y, LABEL_COLOR, label);
+        draw_text_centered(fb, font, &format!("{}%", player.armour), (x0 + x1) / 2, value_y, VALUE_COLOR, value);
+
+        let (x0, x1) = col(0.68, 0.84);
+        draw_text_centered(fb, font, "MUNICIÓN", (x0 + x1) / 2, label_y, LABEL_COLOR, label);
+        draw_text_centered(fb, font, &player.ammo.to_string(), (x0 + x1) / 2, value_y, VALUE_COLOR, value);
+
+        // Llaves: un cuadro por color que lleva encima
+        let (x0, x1) = col(0.84, 1.0);
+        draw_text_centered(fb, font, "LLAVES", (x0 + x1) / 2, label_y, LABEL_COLOR, label);
+        let size = (bar_h / 4).max(4);
+        let keys = player.inventory.keys();
+        let row_w = keys.len() * size + keys.len().saturating_sub(1) * size / 2;
+        let mut kx = ((x0 + x1) / 2).saturating_sub(row_w / 2);
+        for color in keys {
+            fb.fill_rect(kx, value_y + size / 2, size, size, color.rgb());
+            kx += size + size / 2;
+        }
+
+        // Separadores entre columnas
+        for from in [0.24, 0.40, 0.52, 0.68, 0.84] {
+            fb.fill_rect(col(from, from).0, top + bar_h / 8, 1, bar_h * 3 / 4, BORDER_COLOR);
+        }
+
+        // Aviso arriba al centro, con sombra para que se lea sobre cualquier pared
+        if let Some(text) = self.message() {
+            let size = bar_h as f32 * 0.3;
+            let y = h / 12;
+            draw_text_centered(fb, font, text, w / 2 + 1, y + 1, 0x000000, size);
+            draw_text_centered(fb, font, text, w / 2, y, 0xFFFFFF, size);
+        }
+    }
+
+    /// Retrato escalado a `side`×`side`; se tiñe de rojo a medida que baja la salud
+    fn draw_portrait(&self, fb: &mut Framebuffer, player: &Player, x: usize, y: usize, side: usize) {
+        let Some(img) = &self.portrait else { return };
+        let hurt = 1.0 - player.health.clamp(0, MAX_HEALTH) as f32 / MAX_HEALTH as f32;
+        for dy in 0..side {
+            for dx in 0..side {
+                let px = img.get_pixel(
+                    (dx * img.width() as usize / side) as u32,
+                    (dy * img.height() as usize / side) as u32,
+                ).0;
+                let r = px[0] as f32 + (255.0 - px[0] as f32) * hurt * 0.6;
+                let g = px[1] as f32 * (1.0 - hurt * 0.6);
+                let b = px[2] as f32 * (1.0 - hurt * 0.6);
+                fb.point(x + dx, y + dy, ((r as u32) << 16) | ((g as u32) << 8) | b as u32);
+            }
+        }
+    }
+}
+
+// ============================================================================
+// Arma en primera persona
+// ============================================================================
+
+/// Segundos que se ve el fogonazo
+const FIRE_FRAME_TIME: f32 = 0.12;
+/// Velocidad del balanceo al caminar (radianes por segundo)
+const BOB_SPEED: f32 = 9.0;
+
+/// Sprite del arma con tres estados: reposo, balanceo al caminar y disparo
+pub struct WeaponView {
+    idle: Option<RgbaImage>,
+    fire: Option<RgbaImage>,
+    phase: f32,      // fase del balanceo
+    bob: f32,        // 0 quieto, 1 caminando (se suaviza)
+    fire_timer: f32,
+}
+
+impl Default for WeaponView {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl WeaponView {
+    pub fn new() -> Self {
+        WeaponView {
+            idle: load_sprite("sprites/arma.png"),
+            fire: load_sprite("sprites/arma_disparo.png"),
+            phase: 0.0,
+            bob: 0.0,
+            fire_timer: 0.0,
+        }
+    }
+
+    pub fn update(&mut self, dt: f32, moving: bool) {
+        let target = if moving { 1.0 } else { 0.0 };
+        self.bob += (target - self.bob) * (dt * 8.0).min(1.0);
+        if self.bob > 0.01 { self.phase += BOB_SPEED * dt; }
+        self.fire_timer = (self.fire_timer - dt).max(0.0);
+    }
+
+    /// Muestra el cuadro de disparo
+    pub fn fire(&mut self) {
+        self.fire_timer = FIRE_FRAME_TIME;
+    }
+
+    /// Dibuja el arma centrada con su base en `bottom`
+    pub fn draw(&self, fb: &mut Framebuffer, bottom: usize) {
+        let firing = self.fire_timer > 0.0;
+        let frame = if firing { &self.fire } else { &self.idle };
+        let Some(img) = frame.as_ref().or(self.idle.as_ref()) else { return };
+
+        let size = fb.height * 2 / 5;
+        let sway_x = self.phase.cos() * self.bob * fb.width as f32 * 0.02;
+        let sway_y = self.phase.sin().abs() * self.bob * fb.height as f32 * 0.025;
+        let recoil = if firing { fb.height as f32 * 0.02 } else { 0.0 };
+        let x0 = fb.width as f32 / 2.0 - size as f32 / 2.0 + sway_x;
+        let y0 = bottom as f32 - size as f32 + sway_y + recoil + size as f32 * 0.08;
+
+        for dy in 0..size {
+            let sy = y0 as isize + dy as isize;
+            if sy < 0 || sy as usize >= bottom { continue; }
+            for dx in 0..size {
+                let sx = x0 as isize + dx as isize;
+                if sx < 0 { continue; }
+                let px = img.get_pixel(
+                    (dx * img.width() as usize / size) as u32,
+                    (dy * img.height() as usize / size) as u32,
+                ).0;
+                if px[3] < 128 { continue; }
+                fb.point(sx as usize, sy as usize, ((px[0] as u32) << 16) | ((px[1] as u32) << 8) | px[2] as u32);
+            }
+        }
+    }
+}
+
+fn load_sprite(path: &str) -> Option<RgbaImage> {
+    match image::open(path) {
+        Ok(img) => Some(img.to_rgba8()),
+        Err(e) => {
+            eprintln!("No se pudo cargar el sprite {}: {}", path, e);
+            None
+        }
+    }
+}
@@ -0,0 +1,99 @@
+use std::collections::HashMap;
+
+/// Colores de llave. En el mapa la llave es minúscula ('r', 'g', 'y')
+/// y la puerta que abre es la mayúscula ('R', 'G', 'Y').
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum KeyColor {
+    Red,
+    Green,
+    Yellow,
+}
+
+impl KeyColor {
+    pub fn from_key_tile(c: char) -> Option<Self> {
+        match c {
+            'r' => Some(KeyColor::Red),
+            'g' => Some(KeyColor::Green),
+            'y' => Some(KeyColor::Yellow),
+            _ => None,
+        }
+    }
+
+    pub fn from_lock_tile(c: char) -> Option<Self> {
+        match c {
+            'R' => Some(KeyColor::Red),
+            'G' => Some(KeyColor::Green),
+            'Y' => Some(KeyColor::Yellow),
+            _ => None,
+        }
+    }
+
+    /// Nombre para mostrar en el HUD
+    pub fn name(&self) -> &'static str {
+        match self {
+            KeyColor::Red => "roja",
+            KeyColor::Green => "verde",
+            KeyColor::Yellow => "amarilla",
+        }
+    }
+
+    pub fn rgb(&self) -> u32 {
+        match self {
+            KeyColor::Red => 0xDC2828,
+            KeyColor::Green => 0x28C846,
+            KeyColor::Yellow => 0xF0D228,
+        }
+    }
+}
+
+/// Objetivos a recolectar (cuentan para ganar el nivel)
+pub fn is_collectible(c: char) -> bool {
+    c == '1' || c == '2' || c == '3'
+}
+
+/// Chaleco: suma armadura al recogerlo
+pub const ARMOUR_TILE: char = 'a';
+/// Caja de balas: suma munición al recogerla
+pub const AMMO_TILE: char = 'm';
+
+/// Cualquier cosa que se pueda recoger del mapa: objetivos, llaves,
+/// armadura y munición
+pub fn is_pickup(c: char) -> bool {
+    is_collectible(c) || KeyColor::from_key_tile(c).is_some() || c == ARMOUR_TILE || c == AMMO_TILE
+}
+
+/// Lo que lleva el jugador: llaves por color y objetos genéricos por tipo
+#[derive(Default)]
+pub struct Inventory {
+    keys: Vec<KeyColor>,
+    items: HashMap<char, u32>,
+}
+
+impl Inventory {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn add_key(&mut self, color: KeyColor) {
+        if !self.keys.contains(&color) {
+            self.keys.push(color);
+        }
+    }
+
+    pub fn has_key(&self, color: KeyColor) -> bool {
+        self.keys.contains(&color)
+    }
+
+    /// Llaves en el orden en que se recogieron
+    pub fn keys(&self) -> &[KeyColor] {
+        &self.keys
+    }
+
+    pub fn add_item(&mut self, item: char) {
+        *self.items.entry(item).or_insert(0) += 1;
+    }
+
+    pub fn item_count(&self, item: char) -> u32 {
+        self.items.get(&item).copied().unwrap_or(0)
+    }
+}
@@ -0,0 +1,237 @@
+use crate::enemy::is_enemy;
+use crate::inventory::is_collectible;
+use crate::map::{self, load_map, parse_grid, MapError};
+use crate::player::Player;
+use crate::raycaster::{Surfaces, DEFAULT_CEILING, DEFAULT_FLOOR};
+use crate::reach::{self, ReachReport};
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::path::Path;
+
+/// Música que suena si el nivel no declara otra
+pub const DEFAULT_MUSIC: &str = "assets/music.ogg";
+
+/// Nivel listo para jugar: la cuadrícula más los metadatos del archivo.
+///
+/// Se carga desde un `.toml` que envuelve la cuadrícula ASCII, o desde un
+/// `.txt` clásico (el spawn sale del marcador `P` o de la primera celda libre).
+#[derive(Clone)]
+pub struct Level {
+    pub name: String,
+    pub maze: Vec<Vec<char>>,
+    pub spawn: Spawn,
+    pub total_items: u32,              // objetos necesarios para ganar
+    pub textures: HashMap<char, String>, // leyenda: carácter -> ruta de textura
+    pub music: Vec<String>, // lista de temas; se repite al terminar
+    pub surfaces: Surfaces,
+    pub enemies: Vec<(usize, usize, char)>, // (columna, fila, tipo) sacados de la cuadrícula
+}
+
+/// Posición inicial en celdas (1.5 = centro de la segunda celda) y ángulo en radianes
+#[derive(Clone, Copy, Debug)]
+pub struct Spawn {
+    pub x: f32,
+    pub y: f32,
+    pub a: f32,
+}
+
+// --- Formato del archivo .toml ---
+
+#[derive(Deserialize)]
+struct LevelFile {
+    name: Option<String>,
+    map: Option<String>,  // ruta a un .txt, relativa al .toml
+    grid: Option<String>, // o la cuadrícula escrita en el mismo archivo
+    spawn: Option<SpawnDef>,
+    win: Option<WinDef>,
+    #[serde(default)]
+    textures: HashMap<String, String>,
+    music: Option<MusicDef>,
+    floor: Option<String>,
+    ceiling: Option<String>,
+}
+
+/// `music = "tema.ogg"` o una lista para tocar en orden
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum MusicDef {
+    Track(String),
+    Playlist(Vec<String>),
+}
+
+#[derive(Deserialize)]
+struct SpawnDef {
+    x: f32,
+    y: f32,
+    #[serde(default)]
+    facing: f32, // grados, 0 = este, 90 = sur
+}
+
+#[derive(Deserialize)]
+struct WinDef {
+    items: Option<u32>, // por defecto todos los objetos del mapa
+}
+
+impl Level {
+    pub fn load(path: &str) -> Result<Level, MapError> {
+        if path.ends_with(".toml") {
+            Self::load_toml(path)
+        } else {
+            Self::load_txt(path)
+        }
+    }
+
+    /// Mapa clásico: solo la cuadrícula, todo lo demás por defecto
+    fn load_txt(path: &str) -> Result<Level, MapError> {
+        let maze = load_map(path)?;
+        let name = Path::new(path).file_stem().map_or(path.to_string(), |s| s.to_string_lossy().into_owned());
+        let mut level = Level::from_grid(&name, maze)?;
+        level.surfaces = Surfaces::load(path);
+        Ok(level)
+    }
+
+    /// Nivel a partir de una cuadrícula en memoria (p. ej. generada), con valores por defecto
+    pub fn from_grid(name: &str, mut maze: Vec<Vec<char>>) -> Result<Level, MapError> {
+        let spawn = match take_spawn_marker(&mut maze) {
+            Some(s) => s,
+            None => default_spawn(&maze)?,
+        };
+        let enemies = take_enemies(&mut maze);
+        Ok(Level {
+            name: name.to_string(),
+            total_items: count_collectibles(&maze),
+            spawn,
+            maze,
+            textures: HashMap::new(),
+            music: vec![DEFAULT_MUSIC.to_string()],
+            surfaces: Surfaces::uniform(DEFAULT_FLOOR, DEFAULT_CEILING),
+            enemies,
+        })
+    }
+
+    fn load_toml(path: &str) -> Result<Level, MapError> {
+        let text = std::fs::read_to_string(path)
+            .map_err(|source| MapError::Io { path: path.to_string(), source })?;
+        let file: LevelFile = toml::from_str(&text)
+            .map_err(|e| MapError::Parse { path: path.to_string(), message: e.to_string() })?;
+        let dir = Path::new(path).parent().unwrap_or(Path::new(""));
+        let resolve = |p: &str| dir.join(p).to_string_lossy().into_owned();
+
+        let (mut maze, mut surfaces) = match (&file.map, &file.grid) {
+            (Some(map), _) => {
+                let map = resolve(map);
+                (load_map(&map)?, Surfaces::load(&map))
+            }
+            (None, Some(grid)) => (parse_grid(grid), Surfaces::load(path)),
+            (None, None) => return Err(MapError::Parse {
+                path: path.to_string(),
+                message: "falta 'map' o 'grid'".to_string(),
+            }),
+        };
+        if let Some(c) = file.floor.as_deref().and_then(|s| s.chars().next()) {
+            surfaces.default_floor = c;
+        }
+        if let Some(c) = file.ceiling.as_deref().and_then(|s| s.chars().next()) {
+            surfaces.default_ceiling = c;
+        }
+
+        // El spawn del archivo tiene prioridad sobre el marcador P
+        let marker = take_spawn_marker(&mut maze);
+        let spawn = match file.spawn {
+            Some(s) => Spawn { x: s.x, y: s.y, a: s.facing.to_radians() },
+            None => match marker {
+                Some(s) => s,
+                None => default_spawn(&maze)?,
+            },
+        };
+
+        let enemies = take_enemies(&mut maze);
+        let textures = file.textures.iter()
+            .filter_map(|(k, v)| k.chars().next().map(|c| (c, resolve(v))))
+            .collect();
+
+        Ok(Level {
+            name: file.name.unwrap_or_else(|| path.to_string()),
+            total_items: file.win.and_then(|w| w.items).unwrap_or_else(|| count_collectibles(&maze)),
+            spawn,
+            maze,
+            textures,
+            music: match file.music {
+                Some(MusicDef::Track(track)) => vec![resolve(&track)],
+                Some(MusicDef::Playlist(tracks)) if !tracks.is_empty() => tracks.iter().map(|t| resolve(t)).collect(),
+                _ => vec![DEFAULT_MUSIC.to_string()],
+            },
+            surfaces,
+            enemies,
+        })
+    }
+
+    /// Revisa la cuadrícula del nivel: caracteres, borde, spawn y objetos
+    /// alcanzables según `report` (de `reachability`, que así corre una sola vez)
+    pub fn validate(&self, report: &ReachReport) -> Vec<MapError> {
+        let legend: Vec<char> = self.textures.keys().copied().collect();
+        let spawn = (self.spawn.x.max(0.0) as usize, self.spawn.y.max(0.0) as usize);
+        let mut errors = map::validate(&self.maze, spawn, &legend);
+        if errors.is_empty() {
+            errors.extend(report.unreachable().map(|o| MapError::UnreachableCollectible {
+                row: o.row,
+                col: o.col,
+                tile: o.tile,
+            }));
+        }
+        errors
+    }
+
+    /// Análisis de alcance desde el spawn del nivel
+    pub fn reachability(&self, radius: f32, block_size: usize) -> ReachReport {
+        let bs = block_size as f32;
+        reach::analyze(&self.maze, self.spawn.x * bs, self.spawn.y * bs, radius, block_size)
+    }
+
+    /// Crea al jugador en el punto de aparición del nivel
+    pub fn spawn_player(&self, block_size: usize, fov: f32) -> Player {
+        let bs = block_size as f32;
+        Player::new(self.spawn.x * bs, self.spawn.y * bs, self.spawn.a, fov)
+    }
+}
+
+/// Busca la 'P' en la cuadrícula, la reemplaza por espacio y devuelve el spawn
+fn take_spawn_marker(maze: &mut [Vec<char>]) -> Option<Spawn> {
+    for (j, row) in maze.iter_mut().enumerate() {
+        if let Some(i) = row.iter().position(|&c| c == 'P') {
+            row[i] = ' ';
+            return Some(Spawn { x: i as f32 + 0.5, y: j as f32 + 0.5, a: 0.0 });
+        }
+    }
+    None
+}
+
+/// Saca los enemigos de la cuadrícula (su celda queda libre) y devuelve dónde estaban
+fn take_enemies(maze: &mut [Vec<char>]) -> Vec<(usize, usize, char)> {
+    let mut enemies = Vec::new();
+    for (j, row) in maze.iter_mut().enumerate() {
+        for (i, c) in row.iter_mut().enumerate() {
+            if is_enemy(*c) {
+                enemies.push((i, j, *c));
+                *c = ' ';
+            }
+        }
+    }
+    enemies
+}
+
+/// Sin spawn declarado: centro de la primera celda libre fuera del borde
+fn default_spawn(maze: &[Vec<char>]) -> Result<Spawn, MapError> {
+    let h = maze.len();
+    for (j, row) in maze.iter().enumerate().take(h.saturating_sub(1)).skip(1) {
+        let w = row.len();
+        if let Some(i) = (1..w.saturating_sub(1)).find(|&i| row[i] == ' ') {
+            return Ok(Spawn { x: i as f32 + 0.5, y: j as f32 + 0.5, a: 0.0 });
+        }
+    }
+    Err(MapError::MissingSpawn)
+}
+
+fn count_collectibles(maze: &[Vec<char>]) -> u32 {
+    maze.iter().flatten().filter(|&&c| is_collectible(c)).count() as u32
+}
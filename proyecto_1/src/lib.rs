@@ -0,0 +1,30 @@
+//! Motor del raycaster: mapas, jugador, render y reglas del juego.
+//! El binario solo arma las pantallas y la ventana encima de esto.
+
+pub mod audio;
+pub mod bindings;
+pub mod config;
+pub mod door;
+pub mod enemy;
+pub mod framebuffer;
+pub mod game;
+pub mod headless;
+pub mod hud;
+pub mod inventory;
+pub mod level;
+pub mod map;
+pub mod mapgen;
+#[cfg(feature = "window")]
+pub mod minifb_platform;
+pub mod pathfind;
+pub mod platform;
+pub mod player;
+pub mod raycaster;
+pub mod reach;
+pub mod replay;
+pub mod settings;
+pub mod textures;
+pub mod weapon;
+pub mod world;
+
+pub use config::BLOCK_SIZE;
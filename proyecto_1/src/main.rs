@@ -1,556 +1,457 @@
-mod framebuffer;
-mod player;
-mod raycaster;
-mod textures;
-
-use framebuffer::Framebuffer;
-use minifb::{Key, Window, WindowOptions};
-use player::Player;
-use raycaster::{render3d, load_maze};
-use rodio::Source;
-use textures::Textures;
-
+use ej_wolfenstain::audio::AudioManager;
+use ej_wolfenstain::framebuffer::Framebuffer;
+use ej_wolfenstain::game::{Game, LevelSource};
+use ej_wolfenstain::headless;
+use ej_wolfenstain::hud::load_font;
+use ej_wolfenstain::level::Level;
+use ej_wolfenstain::mapgen;
+use ej_wolfenstain::minifb_platform::MinifbPlatform;
+use ej_wolfenstain::platform::Platform;
+use ej_wolfenstain::replay::Replay;
+use ej_wolfenstain::settings::{Settings, Video};
+use ej_wolfenstain::world::Outcome;
+use ej_wolfenstain::BLOCK_SIZE;
+
+use std::path::PathBuf;
 use std::time::Instant;
 
-// --- Texto con rusttype ---
-use rusttype::{Font, Scale, point};
-use std::fs::File;
-use std::io::Read;
-use std::io::BufReader;
-use rodio::{Decoder, OutputStream, Sink};
-
-const WIDTH: usize = 640;
-const HEIGHT: usize = 480;
-const BLOCK_SIZE: usize = 64; // Debe coincidir con raycaster y colisiones
-const TOTAL_ITEMS: u32 = 3; // Total de objetos a recolectar
-
-fn main() {
-    let mut window = Window::new(
-        "Raycaster con Objetivos y Texturas",
-        WIDTH,
-        HEIGHT,
-        WindowOptions::default(),
-    )
-    .unwrap();
-
-// Cargar fuente una sola vez
-let font = load_font("fonts/Arial.ttf");
-
-let mut framebuffer = Framebuffer::new(WIDTH, HEIGHT);
-start_screen(&mut window, &mut framebuffer, &font);
-
-// Mostrar menú y obtener nivel seleccionado
-let selected_level = menu(&mut window, &mut framebuffer, &font);
-
-let maze_file = match selected_level {
-    1 => "./maze1.txt",
-    2 => "./maze2.txt",
-    3 => "./maze3.txt",
-    _ => "./maze1.txt",
-};
-
-let mut maze = load_maze(maze_file);
+const USAGE: &str = "\
+Uso:
+  ej_wolfenstain [opciones]            abre el juego
+  ej_wolfenstain validate <mapa>...    revisa niveles sin abrir ventana
+  ej_wolfenstain render <mapa> [--pos X,Y] [--angle GRADOS] [--size AxB] [--out salida.png]
+  ej_wolfenstain benchmark <mapa> [--frames N] [--size AxB]
+  ej_wolfenstain generate <salida.txt> [--seed N] [--rooms] [--size AxB] [--items N]
+  ej_wolfenstain verify-replay <archivo>
+
+Opciones del juego:
+  --level <mapa>       empieza directo en ese nivel
+  --seed <n>           empieza en un nivel aleatorio con esa semilla
+  --width <px>, --height <px>, --fov <grados>
+  --scale <1|2|4|8>    agranda la ventana
+  --windowed, --fullscreen
+  --mute               sin sonido
+  --config <archivo>   usa otro archivo de ajustes
+  --record <salida>    graba la partida
+  --replay <archivo>   reproduce una grabación
+";
+
+/// Opciones del modo con ventana. Las de video pisan los ajustes solo por
+/// esta vez; no se guardan.
+#[derive(Default)]
+struct Options {
+    level: Option<LevelSource>,
+    config: Option<PathBuf>,
+    record: Option<String>,
+    replay: Option<String>,
+    mute: bool,
+    width: Option<usize>,
+    height: Option<usize>,
+    fov: Option<f32>,
+    scale: Option<usize>,
+    fullscreen: Option<bool>,
+}
 
-    let mut buffer = vec![0u32; WIDTH * HEIGHT];
+impl Options {
+    fn parse(args: &[String]) -> Result<Options, String> {
+        let mut opts = Options { config: Settings::default_path(), ..Options::default() };
+        let mut it = args.iter();
+        while let Some(flag) = it.next() {
+            let mut value = || it.next().map(String::as_str).ok_or(format!("falta el valor de {}", flag));
+            match flag.as_str() {
+                "--level" => opts.level = Some(LevelSource::File(value()?.to_string())),
+                "--seed" => opts.level = Some(LevelSource::Random(number(flag, value()?)?)),
+                "--config" => opts.config = Some(PathBuf::from(value()?)),
+                "--record" => opts.record = Some(value()?.to_string()),
+                "--replay" => opts.replay = Some(value()?.to_string()),
+                "--mute" => opts.mute = true,
+                "--width" => opts.width = Some(number(flag, value()?)?),
+                "--height" => opts.height = Some(number(flag, value()?)?),
+                "--fov" => opts.fov = Some(number(flag, value()?)?),
+                "--scale" => opts.scale = Some(number(flag, value()?)?),
+                "--windowed" => opts.fullscreen = Some(false),
+                "--fullscreen" => opts.fullscreen = Some(true),
+                other => return Err(format!("opción desconocida: {}", other)),
+            }
+        }
+        if opts.level.is_some() && opts.replay.is_some() {
+            return Err("--replay ya trae su nivel; no va con --level ni --seed".to_string());
+        }
+        Ok(opts)
+    }
 
-    
-    let textures = Textures::new();
+    fn apply(&self, video: &mut Video) {
+        video.width = self.width.unwrap_or(video.width);
+        video.height = self.height.unwrap_or(video.height);
+        video.fov = self.fov.unwrap_or(video.fov);
+        video.scale = self.scale.unwrap_or(video.scale);
+        video.fullscreen = self.fullscreen.unwrap_or(video.fullscreen);
+    }
+}
 
-    let mut player = Player::new(
-        150.0, // x
-        150.0, // y
-        0.0,   // ángulo
-        std::f32::consts::FRAC_PI_2, // FOV ~ 90°
-    );
+fn main() {
+    let args: Vec<String> = std::env::args().collect();
+    // Subcomandos sin ventana (las formas con guiones son las de antes)
+    let command = args.get(1).map(String::as_str).unwrap_or("");
+    let rest = args.get(2..).unwrap_or(&[]);
+    let code = match command {
+        "validate" | "--check-map" => Some(validate(rest)),
+        "render" | "--render" => Some(with_path(rest, render_map)),
+        "benchmark" => Some(with_path(rest, benchmark)),
+        "generate" | "--generate" => Some(with_path(rest, generate_map)),
+        "verify-replay" | "--verify-replay" => Some(with_path(rest, |path, _| verify_replay(path))),
+        "help" | "--help" | "-h" => {
+            print!("{}", USAGE);
+            Some(0)
+        }
+        _ => None,
+    };
+    if let Some(code) = code {
+        std::process::exit(code);
+    }
 
-    // --- Música de fondo ---
-    let (_stream, stream_handle) = OutputStream::try_default().unwrap();
-    let sink = Sink::try_new(&stream_handle).unwrap();
+    let options = match Options::parse(&args[1..]) {
+        Ok(options) => options,
+        Err(e) => {
+            eprintln!("{}\n\n{}", e, USAGE);
+            std::process::exit(2);
+        }
+    };
+    let replay = options.replay.as_ref().map(|path| Replay::load(path).unwrap_or_else(|e| {
+        eprintln!("{}", e);
+        std::process::exit(1);
+    }));
+
+    let (mut settings, config) = load_settings(options.config.clone());
+    for warning in settings.validate() {
+        eprintln!("Ajustes: {}", warning);
+    }
+    // Los cambios de la línea de comandos se validan aparte para no guardarlos
+    let mut session = settings.clone();
+    options.apply(&mut session.video);
+    for warning in session.validate() {
+        eprintln!("Opciones: {}", warning);
+    }
+    let video = session.video;
 
-    // Cargar archivo de música
-    let file = BufReader::new(File::open("assets/music.ogg").unwrap());
-    let source = Decoder::new(file).unwrap();
+    let audio = if options.mute { AudioManager::null() } else { AudioManager::new() };
+    let mut platform = MinifbPlatform::new("Raycaster con Objetivos y Texturas", &video, audio).unwrap();
 
-    // Reproducir en bucle
-    sink.append(source.repeat_infinite());
-    sink.play();
+    // Cargar fuente una sola vez
+    let font = load_font("fonts/Arial.ttf");
 
-    // Velocidades
-    let base_speed = 4.0;      // velocidad normal
-    let run_multiplier = 1.8;  // factor de correr
-    let rot_speed = 0.05;
+    let mut framebuffer = Framebuffer::new(video.width, video.height);
+    let mut game = Game::new(font, video.width, video.height);
+    game.set_settings(settings, config, &mut platform);
+    game.set_video(video);
+    if options.record.is_some() {
+        game.record();
+    }
+    if let Some(source) = options.level
+        && let Err(e) = game.start(source, &mut platform)
+    {
+        eprintln!("No se pudo cargar el nivel: {}", e);
+        std::process::exit(1);
+    }
+    if let Some(replay) = replay
+        && let Err(e) = game.play(replay, &mut platform)
+    {
+        eprintln!("No se pudo cargar el nivel de la repetición: {}", e);
+        std::process::exit(1);
+    }
 
     // FPS
     let mut last_frame_time = Instant::now();
 
-    while window.is_open() && !window.is_key_down(Key::Escape) {
-        // Delta time y FPS
+    // Un solo bucle para todas las pantallas
+    while platform.is_open() {
+        // Delta time
         let now = Instant::now();
         let delta_time = now.duration_since(last_frame_time);
         last_frame_time = now;
-        let fps = 1.0 / delta_time.as_secs_f32();
-
-        // Limpiar
-        framebuffer.clear(0x000000);
-
-        // --- VELOCIDAD VARIABLE ---
-        let mut move_speed = base_speed;
-        if window.is_key_down(Key::LeftShift) {
-            move_speed *= run_multiplier; // correr con SHIFT
-        }
-
-        // Controles
-        if window.is_key_down(Key::Left) || window.is_key_down(Key::A) {
-            player.rotate_left(rot_speed);
-        }
-        if window.is_key_down(Key::Right) || window.is_key_down(Key::D) {
-            player.rotate_right(rot_speed);
-        }
-        if window.is_key_down(Key::Up) || window.is_key_down(Key::W) {
-            player.move_forward(move_speed, &maze, BLOCK_SIZE);
-        }
-        if window.is_key_down(Key::Down) || window.is_key_down(Key::S) {
-            player.move_backward(move_speed, &maze, BLOCK_SIZE);
-        }
-          if window.is_key_pressed(Key::X, minifb::KeyRepeat::No) {
-            // Lanzar un rayo hacia adelante para detectar objetos
-            let hit = raycaster::cast_ray(&maze, &player, player.a, BLOCK_SIZE);
-            
-            if let Some(obj_type) = hit.object_type {
-                if hit.distance < 50.0 { // Solo si está cerca
-                    println!("Recolectado objeto: {}", obj_type);
-                    player.collect_item();
-                    
-                    // "Eliminar" el objeto del mapa (reemplazar con espacio)
-                    let obj_x = (player.pos.x + player.a.cos() * hit.distance) as usize / BLOCK_SIZE;
-                    let obj_y = (player.pos.y + player.a.sin() * hit.distance) as usize / BLOCK_SIZE;
-                    
-                    if obj_y < maze.len() && obj_x < maze[0].len() {
-                        maze[obj_y][obj_x] = ' ';
-                    }
-                    
-                    // Reproducir sonido de recolección
-                    play_sound("assets/collect.ogg");
-                }
-            }
-        }
-         // Render 3D
-         render3d(&mut framebuffer, &player, &maze, BLOCK_SIZE, &textures);
-        
-        // Renderizar sprites de objetos
-        
-
-        
-        // Detectar si el jugador está mirando un objeto y presiona E para recolectar
-        if window.is_key_pressed(Key::E, minifb::KeyRepeat::No) {
-            // Lanzar un rayo hacia adelante para detectar objetos
-            let hit = raycaster::cast_ray(&maze, &player, player.a, BLOCK_SIZE);
-            
-            if let Some(obj_type) = hit.object_type {
-                if hit.distance < 50.0 { // Solo si está cerca
-                    println!("Recolectado objeto: {}", obj_type);
-                    player.collect_item();
-                    
-                    // "Eliminar" el objeto del mapa (reemplazar con espacio)
-                    let obj_x = (player.pos.x + player.a.cos() * hit.distance) as usize / BLOCK_SIZE;
-                    let obj_y = (player.pos.y + player.a.sin() * hit.distance) as usize / BLOCK_SIZE;
-                    
-                    if obj_y < maze.len() && obj_x < maze[0].len() {
-                        maze[obj_y][obj_x] = ' ';
-                    }
-                    
-                    // Reproducir sonido de recolección
-                    play_sound("assets/collect.wav");
-                }
-            }
-        }
 
-        // Render 3D
-        render3d(&mut framebuffer, &player, &maze, BLOCK_SIZE, &textures);
-
-        // Minimap (esquina superior izquierda)
-        render_minimap(&mut framebuffer, &player, &maze, 8, 8, 6);
-
-        // Mostrar contador de objetos recolectados
-        let items_text = format!("Objetos: {}/{}", player.get_collected_items(), TOTAL_ITEMS);
-        draw_text(
-            &mut framebuffer,
-            &font,
-            &items_text,
-            14,
-            14,
-            0xFFFF00,
-            18.0,
-        );
-
-        // FPS (esquina superior derecha)
-        let fps_text = format!("FPS: {:.0}", fps);
-        draw_text(
-            &mut framebuffer,
-            &font,
-            &fps_text,
-            WIDTH.saturating_sub(140),
-            14,
-            0xFFFF00,
-            18.0,
-        );
-
-        // Volcar al buffer lineal y mostrar
-        framebuffer.flush_to(&mut buffer);
-        window.update_with_buffer(&buffer, WIDTH, HEIGHT).unwrap();
-        
-        // Comprobar si se han recolectado todos los objetos
-        if player.has_all_items(TOTAL_ITEMS) {
-            victory_screen(&mut window, &mut framebuffer, &font);
+        if !game.frame(delta_time.as_secs_f32(), &mut platform, &mut framebuffer) {
             break;
         }
+        platform.present(&framebuffer);
     }
-}
-
-fn start_screen(window: &mut Window, framebuffer: &mut Framebuffer, font: &Font) {
-    let mut buffer = vec![0u32; WIDTH * HEIGHT];
-
-    // Cargar imagen de fondo (asegúrate que exista en assets/)
-    let mut img = image::open("assets/Radioheadkida.png")
-    .expect("No se pudo cargar la imagen de fondo de inicio")
-    .resize_exact(WIDTH as u32, HEIGHT as u32, image::imageops::FilterType::Nearest);
-
-    // Convertir a RGB y escalar a la resolución de la ventana
-    let img = img.resize(WIDTH as u32, HEIGHT as u32, image::imageops::FilterType::Triangle);
-    let img_buf = img.to_rgb8();
-
-    loop {
-        // Copiar imagen como fondo
-        for y in 0..HEIGHT {
-            for x in 0..WIDTH {
-                let px = img_buf.get_pixel(x as u32, y as u32);
-                let r = px[0] as u32;
-                let g = px[1] as u32;
-                let b = px[2] as u32;
-                framebuffer.point(x, y, (r << 16) | (g << 8) | b);
-            }
-        }
 
-        // Texto encima
-        draw_text(
-            framebuffer,
-            font,
-            "atrapa el radio objeto",
-            WIDTH / 2 - 160,
-            HEIGHT / 2 - 50,
-            0xFFFFFF,
-            40.0,
-        );
-
-        draw_text(
-            framebuffer,
-            font,
-            "Presiona ENTER para comenzar",
-            WIDTH / 2 - 200,
-            HEIGHT / 2 + 20,
-            0xFFFF00,
-            24.0,
-        );
-
-        // Mostrar en ventana
-        framebuffer.flush_to(&mut buffer);
-        window.update_with_buffer(&buffer, WIDTH, HEIGHT).unwrap();
-
-        // Esperar ENTER
-        if window.is_key_down(Key::Enter) {
-            break;
+    if let Some(path) = options.record {
+        match game.recording() {
+            Some(replay) => match replay.save(&path) {
+                Ok(()) => println!("Partida grabada en {} ({} pasos)", path, replay.ticks.len()),
+                Err(e) => eprintln!("No se pudo guardar {}: {}", path, e),
+            },
+            None => println!("No se jugó ninguna partida; no se guardó {}", path),
         }
+    }
+}
 
-        if !window.is_open() || window.is_key_down(Key::Escape) {
-            std::process::exit(0);
+/// Corre un subcomando que recibe un archivo y opciones; sin archivo muestra el uso
+fn with_path(args: &[String], command: impl Fn(&str, &[String]) -> i32) -> i32 {
+    match args.split_first() {
+        Some((path, flags)) => command(path, flags),
+        None => {
+            eprintln!("Falta el archivo\n\n{}", USAGE);
+            2
         }
     }
 }
-fn menu(window: &mut Window, framebuffer: &mut Framebuffer, font: &Font) -> u8 {
-    let mut buffer = vec![0u32; WIDTH * HEIGHT];
-
-    loop {
-        framebuffer.clear(0x000000);
-
-        draw_text(framebuffer, font, "Selecciona un nivel:", WIDTH/2 - 140, HEIGHT/2 - 100, 0xFFFFFF, 32.0);
-        draw_text(framebuffer, font, "1 - Nivel 1", WIDTH/2 - 60, HEIGHT/2 - 40, 0x00FF00, 24.0);
-        draw_text(framebuffer, font, "2 - Nivel 2", WIDTH/2 - 60, HEIGHT/2, 0x00FF00, 24.0);
-        draw_text(framebuffer, font, "3 - Nivel 3", WIDTH/2 - 60, HEIGHT/2 + 40, 0x00FF00, 24.0);
 
-        framebuffer.flush_to(&mut buffer);
-        window.update_with_buffer(&buffer, WIDTH, HEIGHT).unwrap();
-
-        if window.is_key_down(Key::Key1) { return 1; }
-        if window.is_key_down(Key::Key2) { return 2; }
-        if window.is_key_down(Key::Key3) { return 3; }
-        if !window.is_open() || window.is_key_down(Key::Escape) { std::process::exit(0); }
+/// `validate <mapa>...`: revisa todos y falla si alguno tiene errores
+fn validate(paths: &[String]) -> i32 {
+    if paths.is_empty() {
+        eprintln!("Falta el mapa\n\n{}", USAGE);
+        return 2;
     }
+    paths.iter().map(|path| check_map(path)).max().unwrap_or(0)
 }
-fn render_sprites(
-    fb: &mut Framebuffer,
-    player: &Player,
-    maze: &Vec<Vec<char>>,
-    block_size: usize,
-    textures: &Textures,
-) {
-    let w = fb.width;
-    let h = fb.height;
-    let hh = h as f32 / 2.0;
-    
-    // Buscar todos los objetos en el laberinto
-    for (y, row) in maze.iter().enumerate() {
-        for (x, &cell) in row.iter().enumerate() {
-            // Solo procesar celdas que contienen objetos (1, 2, 3)
-            if cell != '1' && cell != '2' && cell != '3' {
-                continue;
-            }
-            
-            // Calcular posición del objeto en coordenadas del mundo
-            let obj_x = (x * block_size) as f32 + block_size as f32 / 2.0;
-            let obj_y = (y * block_size) as f32 + block_size as f32 / 2.0;
-            
-            // Calcular vector desde el jugador al objeto
-            let dx = obj_x - player.pos.x;
-            let dy = obj_y - player.pos.y;
-            
-            // Calcular distancia y ángulo del objeto
-            let dist = (dx * dx + dy * dy).sqrt();
-            let mut angle = dy.atan2(dx) - player.a;
-            
-            // Normalizar el ángulo
-            while angle > std::f32::consts::PI { angle -= 2.0 * std::f32::consts::PI; }
-            while angle < -std::f32::consts::PI { angle += 2.0 * std::f32::consts::PI; }
-            
-            // Solo renderizar objetos en el campo de visión
-            if angle.abs() < player.fov / 2.0 {
-                // Calcular la posición horizontal en la pantalla
-                let screen_x = (0.5 + angle / player.fov) * w as f32;
-                
-                // Calcular el tamaño del sprite en pantalla
-                let sprite_size = (block_size as f32 * hh / dist) as usize;
-                let half_size = sprite_size / 2;
-                
-                // Calcular coordenadas de pantalla
-                let screen_start_x = (screen_x as isize - half_size as isize).max(0) as usize;
-                let screen_end_x = (screen_x as isize + half_size as isize).min(w as isize - 1) as usize;
-                
-                let screen_start_y = (hh as isize - half_size as isize).max(0) as usize;
-                let screen_end_y = (hh as isize + half_size as isize).min(h as isize - 1) as usize;
-                
-                // Renderizar el sprite
-                for sy in screen_start_y..screen_end_y {
-                    for sx in screen_start_x..screen_end_x {
-                        // Calcular coordenadas UV en la textura
-                        let u = (sx as f32 - (screen_x - half_size as f32)) / sprite_size as f32;
-                        let v = (sy as f32 - (hh - half_size as f32)) / sprite_size as f32;
-                        
-                        // Obtener color de la textura
-                        let color = textures.sample(cell, u, v);
-                        
-                        // Solo dibujar si no es transparente (alpha > 0)
-                        if color != 0x000000 {
-                            fb.point(sx, sy, color);
-                        }
-                    }
+
+/// `benchmark <mapa> [--frames N] [--size AxB]`
+fn benchmark(path: &str, flags: &[String]) -> i32 {
+    let level = match Level::load(path) {
+        Ok(level) => level,
+        Err(e) => {
+            eprintln!("{}: {}", path, e);
+            return 1;
+        }
+    };
+    let video = Video::default();
+    let (mut frames, mut width, mut height) = (600, video.width, video.height);
+    let mut it = flags.iter();
+    while let Some(flag) = it.next() {
+        let value = it.next().map(String::as_str).unwrap_or("");
+        match flag.as_str() {
+            "--frames" => match value.parse::<usize>() {
+                Ok(n) if n > 0 => frames = n,
+                _ => {
+                    eprintln!("--frames espera un número y recibió '{}'", value);
+                    return 2;
                 }
+            },
+            "--size" => match parse_pair::<usize>(value, 'x') {
+                Some((w, h)) if w > 0 && h > 0 => (width, height) = (w, h),
+                _ => {
+                    eprintln!("--size espera ANCHOxALTO y recibió '{}'", value);
+                    return 2;
+                }
+            },
+            other => {
+                eprintln!("Opción desconocida: {}", other);
+                return 2;
             }
         }
     }
+
+    let font = load_font("fonts/Arial.ttf");
+    let report = headless::benchmark(level, frames, width, height, &font);
+    let average = report.average().as_secs_f64();
+    println!("{}: {} cuadros de {}x{} en {:.2} s", path, report.frames, width, height, report.total.as_secs_f64());
+    println!("  promedio {:.2} ms ({:.0} FPS), el más lento {:.2} ms",
+        average * 1000.0, 1.0 / average.max(1e-9), report.slowest.as_secs_f64() * 1000.0);
+    0
 }
 
-fn victory_screen(window: &mut Window, framebuffer: &mut Framebuffer, font: &Font) {
-    let mut buffer = vec![0u32; WIDTH * HEIGHT];
-    
-    // Cargar imagen de victoria
-    let mut img = image::open("assets/victory.png")
-        .unwrap_or_else(|_| image::DynamicImage::new_rgb8(WIDTH as u32, HEIGHT as u32))
-        .resize_exact(WIDTH as u32, HEIGHT as u32, image::imageops::FilterType::Nearest);
-    
-    let img_buf = img.to_rgb8();
-    
-    loop {
-        // Fondo con imagen
-        for y in 0..HEIGHT {
-            for x in 0..WIDTH {
-                let px = img_buf.get_pixel(x as u32, y as u32);
-                let r = px[0] as u32;
-                let g = px[1] as u32;
-                let b = px[2] as u32;
-                framebuffer.point(x, y, (r << 16) | (g << 8) | b);
+/// Ajustes del archivo, o los de siempre si no existe o no se puede leer.
+/// Devuelve también dónde guardarlos: si el archivo tiene errores no se
+/// escribe encima, para no perder lo que el usuario puso ahí.
+fn load_settings(path: Option<PathBuf>) -> (Settings, Option<PathBuf>) {
+    match path {
+        Some(path) if path.exists() => match Settings::load(&path) {
+            Ok(settings) => (settings, Some(path)),
+            Err(e) => {
+                eprintln!("Ajustes por defecto (no se guardarán cambios): {}", e);
+                (Settings::default(), None)
             }
+        },
+        path => (Settings::default(), path),
+    }
+}
+
+/// Repite la grabación sin ventana; termina bien solo si se gana el nivel
+fn verify_replay(path: &str) -> i32 {
+    let result = Replay::load(path).and_then(|replay| replay.run());
+    match result {
+        Ok((world, Outcome::Won)) => {
+            println!("{}: victoria ({} objetos, salud {})", path, world.player.get_collected_items(), world.player.health);
+            0
         }
-        
-        draw_text(
-            framebuffer,
-            font,
-            "¡FELICIDADES!",
-            WIDTH / 2 - 150,
-            HEIGHT / 2 - 50,
-            0x00FF00,
-            40.0,
-        );
-        
-        draw_text(
-            framebuffer,
-            font,
-            "Has recolectado todos los objetos",
-            WIDTH / 2 - 200,
-            HEIGHT / 2 + 20,
-            0xFFFFFF,
-            24.0,
-        );
-        
-        draw_text(
-            framebuffer,
-            font,
-            "Presiona ESC para salir",
-            WIDTH / 2 - 150,
-            HEIGHT / 2 + 70,
-            0xFFFF00,
-            20.0,
-        );
-
-        framebuffer.flush_to(&mut buffer);
-        window.update_with_buffer(&buffer, WIDTH, HEIGHT).unwrap();
-
-        if window.is_key_down(Key::Escape) {
-            break;
+        Ok((world, outcome)) => {
+            let state = if outcome == Outcome::Lost { "derrota" } else { "la grabación termina sin ganar" };
+            println!("{}: {} ({} de {} objetos)", path, state, world.player.get_collected_items(), world.level.total_items);
+            1
+        }
+        Err(e) => {
+            eprintln!("{}", e);
+            1
         }
     }
 }
 
-fn play_sound(path: &str) {
-    // Implementación básica para reproducir sonido
-    if let Ok(file) = File::open(path) {
-        let source = Decoder::new(BufReader::new(file)).unwrap();
-        let (_stream, stream_handle) = OutputStream::try_default().unwrap();
-        let _result = stream_handle.play_raw(source.convert_samples());
+/// Carga y valida un nivel, imprime los problemas y devuelve el código de salida
+fn check_map(path: &str) -> i32 {
+    let level = match Level::load(path) {
+        Ok(level) => level,
+        Err(e) => {
+            eprintln!("{}: {}", path, e);
+            return 1;
+        }
+    };
+    let player = level.spawn_player(BLOCK_SIZE, std::f32::consts::FRAC_PI_2);
+    let report = level.reachability(player.radius, BLOCK_SIZE);
+    let errors = level.validate(&report);
+
+    // Distancia a pie desde el spawn hasta cada objetivo
+    for o in &report.objectives {
+        match o.steps {
+            Some(steps) => println!("  '{}' (fila {}, columna {}): {} celdas ({:.0} unidades)",
+                o.tile, o.row, o.col, steps, o.distance.unwrap_or(0.0)),
+            None => println!("  '{}' (fila {}, columna {}): inalcanzable", o.tile, o.row, o.col),
+        }
+    }
+    let reachable = report.reachable.iter().flatten().filter(|&&r| r).count();
+    println!("  {} celdas alcanzables", reachable);
+    for zone in &report.dead_zones {
+        let (col, row) = zone[0];
+        println!("  zona muerta de {} celdas desde fila {}, columna {}", zone.len(), row, col);
     }
-}
 
-// ============================================================================
-// Minimap (con jugador correctamente escalado)
-// ============================================================================
-fn render_minimap(
-    fb: &mut Framebuffer,
-    player: &Player,
-    maze: &Vec<Vec<char>>,
-    x_off: usize,
-    y_off: usize,
-    scale: usize, // píxeles por celda en el minimapa
-) {
-    // Colores por tipo de pared SOLO para el minimapa
-    fn wall_color(c: char) -> u32 {
-        match c {
-            '#' => 0x808080, // gris
-            'A' => 0xCC3333, // rojo
-            'B' => 0x33CC33, // verde
-            'C' => 0x3333CC, // azul
-            '1' => 0xFF0000, // objeto 1 rojo
-            '2' => 0x00FF00, // objeto 2 verde
-            '3' => 0x0000FF, // objeto 3 azul
-            _   => 0x606060, // otros
+    if errors.is_empty() {
+        println!("{}: OK ({} objetos)", path, level.total_items);
+        0
+    } else {
+        for e in &errors {
+            eprintln!("{}: {}", path, e);
         }
+        1
     }
+}
 
-    // Celdas del mapa
-    for (j, row) in maze.iter().enumerate() {
-        for (i, &cell) in row.iter().enumerate() {
-            let color = if cell == ' ' { 0x000000 } else { wall_color(cell) };
-            for dx in 0..scale {
-                for dy in 0..scale {
-                    let px = x_off + i * scale + dx;
-                    let py = y_off + j * scale + dy;
-                    if px < fb.width && py < fb.height {
-                        fb.point(px, py, color);
+/// `--generate <salida.txt> [--seed N] [--rooms] [--size AxB] [--items N]`
+fn generate_map(out: &str, flags: &[String]) -> i32 {
+    let mut opts = mapgen::GenOptions::default();
+    let mut it = flags.iter();
+    while let Some(flag) = it.next() {
+        let mut value = || it.next().map(String::as_str).unwrap_or("");
+        match flag.as_str() {
+            "--seed" => {
+                let value = value();
+                match value.parse() {
+                    Ok(seed) => opts.seed = seed,
+                    Err(_) => {
+                        eprintln!("--seed espera un número entero y recibió '{}'", value);
+                        return 2;
                     }
                 }
             }
-        }
-    }
-
-    // Jugador: convertir coordenadas del mundo -> minimapa
-    let px = x_off as f32 + (player.pos.x / BLOCK_SIZE as f32) * scale as f32;
-    let py = y_off as f32 + (player.pos.y / BLOCK_SIZE as f32) * scale as f32;
-
-    // Dibujar al jugador como un disco pequeño
-    let r = (scale as i32 / 3).max(2); // radio
-    for dy in -r..=r {
-        for dx in -r..=r {
-            if dx*dx + dy*dy <= r*r {
-                let mx = px as i32 + dx;
-                let my = py as i32 + dy;
-                if mx >= 0 && my >= 0 && (mx as usize) < fb.width && (my as usize) < fb.height {
-                    fb.point(mx as usize, my as usize, 0xFFFF00);
+            "--items" => {
+                let value = value();
+                match value.parse() {
+                    Ok(items) => opts.items = items,
+                    Err(_) => {
+                        eprintln!("--items espera una cantidad y recibió '{}'", value);
+                        return 2;
+                    }
                 }
             }
+            "--rooms" => opts.kind = mapgen::MazeKind::Rooms,
+            "--size" => {
+                let value = value();
+                match parse_pair::<usize>(value, 'x') {
+                    Some((w, h)) if w > 0 && h > 0 => (opts.width, opts.height) = (w, h),
+                    _ => {
+                        eprintln!("--size espera ANCHOxALTO y recibió '{}'", value);
+                        return 2;
+                    }
+                }
+            }
+            other => {
+                eprintln!("Opción desconocida: {}", other);
+                return 2;
+            }
         }
     }
 
-    // Pequeño "heading" (línea) indicando hacia dónde mira
-    let len = (scale as f32 * 0.9).max(4.0);
-    let tip_x = px + player.a.cos() * len;
-    let tip_y = py + player.a.sin() * len;
-    draw_line(fb, px as i32, py as i32, tip_x as i32, tip_y as i32, 0xFFFF00);
+    let maze = mapgen::generate(&opts);
+    if let Err(e) = std::fs::write(out, mapgen::to_text(&maze)) {
+        eprintln!("No se pudo escribir {}: {}", out, e);
+        return 1;
+    }
+    println!("{} generado (semilla {})", out, opts.seed);
+    0
 }
 
-// Bresenham para la línea del heading en el minimapa
-fn draw_line(fb: &mut Framebuffer, x0: i32, y0: i32, x1: i32, y1: i32, color: u32) {
-    let mut x0 = x0;
-    let mut y0 = y0;
-    let dx = (x1 - x0).abs();
-    let sx = if x0 < x1 { 1 } else { -1 };
-    let dy = -(y1 - y0).abs();
-    let sy = if y0 < y1 { 1 } else { -1 };
-    let mut err = dx + dy;
-
-    loop {
-        if x0 >= 0 && y0 >= 0 && (x0 as usize) < fb.width && (y0 as usize) < fb.height {
-            fb.point(x0 as usize, y0 as usize, color);
+/// `--render <mapa> [--pos X,Y] [--angle GRADOS] [--size AxB] [--out salida.png]`
+///
+/// La posición va en unidades del mundo; sin `--pos`/`--angle` usa el spawn del nivel.
+fn render_map(path: &str, flags: &[String]) -> i32 {
+    let level = match Level::load(path) {
+        Ok(level) => level,
+        Err(e) => {
+            eprintln!("{}: {}", path, e);
+            return 1;
+        }
+    };
+    let bs = BLOCK_SIZE as f32;
+    let video = Video::default();
+    let mut camera = headless::Camera {
+        x: level.spawn.x * bs,
+        y: level.spawn.y * bs,
+        a: level.spawn.a,
+        width: video.width,
+        height: video.height,
+    };
+    let mut out = "shot.png".to_string();
+
+    let mut it = flags.iter();
+    while let Some(flag) = it.next() {
+        let value = it.next().map(String::as_str).unwrap_or("");
+        match flag.as_str() {
+            "--pos" => match parse_pair::<f32>(value, ',') {
+                Some((x, y)) => (camera.x, camera.y) = (x, y),
+                None => {
+                    eprintln!("--pos espera X,Y y recibió '{}'", value);
+                    return 2;
+                }
+            },
+            "--angle" => match value.parse::<f32>() {
+                Ok(deg) => camera.a = deg.to_radians(),
+                Err(_) => {
+                    eprintln!("--angle espera grados y recibió '{}'", value);
+                    return 2;
+                }
+            },
+            "--size" => match parse_pair::<usize>(value, 'x') {
+                Some((w, h)) if w > 0 && h > 0 => (camera.width, camera.height) = (w, h),
+                _ => {
+                    eprintln!("--size espera ANCHOxALTO y recibió '{}'", value);
+                    return 2;
+                }
+            },
+            "--out" => out = value.to_string(),
+            other => {
+                eprintln!("Opción desconocida: {}", other);
+                return 2;
+            }
         }
-        if x0 == x1 && y0 == y1 { break; }
-        let e2 = 2 * err;
-        if e2 >= dy { err += dy; x0 += sx; }
-        if e2 <= dx { err += dx; y0 += sy; }
     }
-}
 
-// ============================================================================
-// Texto (FPS) con rusttype
-// ============================================================================
+    let framebuffer = headless::render_level(&level, &camera, BLOCK_SIZE);
+    if let Err(e) = framebuffer.save_png(&out) {
+        eprintln!("No se pudo guardar {}: {}", out, e);
+        return 1;
+    }
+    println!("{} guardado ({}x{})", out, camera.width, camera.height);
+    0
+}
 
-fn load_font(path: &str) -> Font<'static> {
-    let mut data = Vec::new();
-    File::open(path).expect("No se pudo abrir la fuente (fonts/Arial.ttf)")
-        .read_to_end(&mut data)
-        .expect("No se pudo leer la fuente");
-    Font::try_from_vec(data).expect("Fuente inválida o corrupta")
+/// Valor numérico de una opción, con el error listo para mostrar
+fn number<T: std::str::FromStr>(flag: &str, value: &str) -> Result<T, String> {
+    value.parse().map_err(|_| format!("{} no acepta '{}'", flag, value))
 }
 
-/// Dibuja texto sólido (sin blending con fondo) con color RGB 0xRRGGBB
-fn draw_text(fb: &mut Framebuffer, font: &Font<'_>, text: &str, x: usize, y: usize, color: u32, size: f32) {
-    let scale = Scale { x: size, y: size };
-    let v_metrics = font.v_metrics(scale);
-    let mut cursor_x = x as f32;
-    let baseline_y = y as f32 + v_metrics.ascent;
-
-    for ch in text.chars() {
-        let glyph = font.glyph(ch).scaled(scale).positioned(point(cursor_x, baseline_y));
-        if let Some(bb) = glyph.pixel_bounding_box() {
-            glyph.draw(|gx, gy, v| {
-                if v == 0.0 { return; }
-                let px = bb.min.x + gx as i32;
-                let py = bb.min.y + gy as i32;
-                if px >= 0 && py >= 0 && (px as usize) < fb.width && (py as usize) < fb.height {
-                    // Escalamos el color por la cobertura 'v' para antialias
-                    let r = ((color >> 16) & 0xFF) as f32 * v;
-                    let g = ((color >> 8) & 0xFF) as f32 * v;
-                    let b = (color & 0xFF) as f32 * v;
-                    let col = ((r as u32) << 16) | ((g as u32) << 8) | (b as u32);
-                    fb.point(px as usize, py as usize, col);
-                }
-            });
-        }
-        cursor_x += glyph.unpositioned().h_metrics().advance_width;
-    }
-}
\ No newline at end of file
+/// "a,b" o "AxB" -> (a, b)
+fn parse_pair<T: std::str::FromStr>(value: &str, sep: char) -> Option<(T, T)> {
+    let (a, b) = value.split_once(sep)?;
+    Some((a.trim().parse().ok()?, b.trim().parse().ok()?))
+}
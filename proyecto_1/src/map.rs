@@ -1,13 +1,75 @@
+use crate::door::is_door;
+use crate::inventory::is_pickup;
+use std::fmt;
 use std::fs::read_to_string;
 
-pub fn load_map(path: &str) -> Vec<Vec<char>> {
-    let contents = read_to_string(path).expect("No se pudo leer el archivo del mapa");
-    let mut lines: Vec<Vec<char>> = contents
+/// Errores al cargar o validar un mapa
+#[derive(Debug)]
+pub enum MapError {
+    Io { path: String, source: std::io::Error },
+    Parse { path: String, message: String },
+    UnknownTile { row: usize, col: usize, tile: char },
+    OpenBorder { row: usize, col: usize },
+    UnreachableCollectible { row: usize, col: usize, tile: char },
+    MissingSpawn,
+    BlockedSpawn { row: usize, col: usize },
+}
+
+impl fmt::Display for MapError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MapError::Io { path, source } => write!(f, "no se pudo leer {}: {}", path, source),
+            MapError::Parse { path, message } => write!(f, "{} inválido: {}", path, message),
+            MapError::UnknownTile { row, col, tile } =>
+                write!(f, "carácter desconocido '{}' en fila {}, columna {}", tile, row, col),
+            MapError::OpenBorder { row, col } =>
+                write!(f, "el borde está abierto en fila {}, columna {}", row, col),
+            MapError::UnreachableCollectible { row, col, tile } =>
+                write!(f, "el objeto '{}' en fila {}, columna {} no se puede alcanzar", tile, row, col),
+            MapError::MissingSpawn => write!(f, "el mapa no tiene punto de aparición"),
+            MapError::BlockedSpawn { row, col } =>
+                write!(f, "el punto de aparición (fila {}, columna {}) no está libre", row, col),
+        }
+    }
+}
+
+impl std::error::Error for MapError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            MapError::Io { source, .. } => Some(source),
+            _ => None,
+        }
+    }
+}
+
+/// Paredes con textura propia del juego
+pub fn is_wall(c: char) -> bool {
+    matches!(c, '#' | 'A' | 'B' | 'C')
+}
+
+/// Caracteres que entiende el motor sin necesidad de leyenda
+pub fn is_known_tile(c: char) -> bool {
+    c == ' ' || c == 'P' || is_wall(c) || is_door(c) || is_pickup(c)
+}
+
+pub fn load_map(path: &str) -> Result<Vec<Vec<char>>, MapError> {
+    let contents = read_to_string(path)
+        .map_err(|source| MapError::Io { path: path.to_string(), source })?;
+    Ok(parse_grid(&contents))
+}
+
+/// Convierte texto en cuadrícula. Ignora líneas vacías al principio y al final
+/// y rellena con espacios las filas cortas para que todas tengan el mismo ancho.
+pub fn parse_grid(text: &str) -> Vec<Vec<char>> {
+    let mut lines: Vec<Vec<char>> = text
         .lines()
+        .skip_while(|l| l.trim().is_empty())
         .map(|line| line.chars().collect())
         .collect();
+    while lines.last().is_some_and(|l| l.iter().all(|c| c.is_whitespace())) {
+        lines.pop();
+    }
 
-    // Validación simple: todas las filas deben tener el mismo ancho
     let max_w = lines.iter().map(|r| r.len()).max().unwrap_or(0);
     for r in lines.iter_mut() {
         if r.len() < max_w {
@@ -17,3 +79,31 @@ pub fn load_map(path: &str) -> Vec<Vec<char>> {
 
     lines
 }
+
+/// Revisa caracteres, borde y spawn; devuelve todos los problemas (vacío si está bien).
+/// `extra_tiles` son los caracteres que el nivel define en su leyenda de texturas.
+/// El alcance de los objetos lo revisa `reach::analyze`.
+pub fn validate(maze: &[Vec<char>], spawn: (usize, usize), extra_tiles: &[char]) -> Vec<MapError> {
+    let mut errors = Vec::new();
+    let h = maze.len();
+    let w = maze.first().map_or(0, |r| r.len());
+
+    for (row, line) in maze.iter().enumerate() {
+        for (col, &tile) in line.iter().enumerate() {
+            if !is_known_tile(tile) && !extra_tiles.contains(&tile) {
+                errors.push(MapError::UnknownTile { row, col, tile });
+            }
+            let on_border = row == 0 || col == 0 || row + 1 == h || col + 1 == w;
+            if on_border && (tile == ' ' || tile == 'P' || is_door(tile) || is_pickup(tile)) {
+                errors.push(MapError::OpenBorder { row, col });
+            }
+        }
+    }
+
+    let (sx, sy) = spawn;
+    if sy >= h || sx >= w || maze[sy][sx] != ' ' {
+        errors.push(MapError::BlockedSpawn { row: sy, col: sx });
+    }
+
+    errors
+}
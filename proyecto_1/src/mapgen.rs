@@ -0,0 +1,243 @@
+use crate::reach::neighbours;
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::{Rng, SeedableRng};
+use std::collections::VecDeque;
+
+/// Texturas de pared que puede usar el generador
+const WALLS: [char; 4] = ['#', 'A', 'B', 'C'];
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MazeKind {
+    /// Laberinto perfecto por backtracking recursivo
+    Backtracker,
+    /// Habitaciones rectangulares unidas por pasillos
+    Rooms,
+}
+
+pub struct GenOptions {
+    pub width: usize,  // se ajusta a impar
+    pub height: usize, // se ajusta a impar
+    pub items: u32,
+    pub kind: MazeKind,
+    pub seed: u64,
+}
+
+impl Default for GenOptions {
+    fn default() -> Self {
+        GenOptions { width: 21, height: 15, items: 3, kind: MazeKind::Backtracker, seed: 0 }
+    }
+}
+
+/// Genera un nivel jugable: borde cerrado, spawn 'P' y `items` objetos
+/// alcanzables y repartidos. El mismo `seed` produce siempre el mismo mapa.
+pub fn generate(opts: &GenOptions) -> Vec<Vec<char>> {
+    let mut rng = StdRng::seed_from_u64(opts.seed);
+    let w = (opts.width.max(5)) | 1;
+    let h = (opts.height.max(5)) | 1;
+
+    let mut open = vec![vec![false; w]; h];
+    let spawn = match opts.kind {
+        MazeKind::Backtracker => carve_backtracker(&mut open, &mut rng),
+        MazeKind::Rooms => carve_rooms(&mut open, &mut rng),
+    };
+
+    // Cada zona de 4x4 celdas recibe una textura de pared distinta
+    let zone_walls: Vec<Vec<char>> = (0..h.div_ceil(4))
+        .map(|_| (0..w.div_ceil(4)).map(|_| *WALLS.choose(&mut rng).unwrap()).collect())
+        .collect();
+
+    let mut maze: Vec<Vec<char>> = (0..h)
+        .map(|y| (0..w).map(|x| {
+            if open[y][x] { ' ' } else { zone_walls[y / 4][x / 4] }
+        }).collect())
+        .collect();
+
+    for (k, (x, y)) in place_items(&open, spawn, opts.items, &mut rng).into_iter().enumerate() {
+        maze[y][x] = ['1', '2', '3'][k % 3];
+    }
+    maze[spawn.1][spawn.0] = 'P';
+    maze
+}
+
+/// Texto listo para guardar como .txt
+pub fn to_text(maze: &Vec<Vec<char>>) -> String {
+    let mut out = String::new();
+    for row in maze {
+        out.extend(row.iter());
+        out.push('\n');
+    }
+    out
+}
+
+/// Excava desde (1,1) saltando de a dos celdas; devuelve el spawn
+fn carve_backtracker(open: &mut [Vec<bool>], rng: &mut StdRng) -> (usize, usize) {
+    let (w, h) = (open[0].len(), open.len());
+    let mut stack = vec![(1usize, 1usize)];
+    open[1][1] = true;
+
+    while let Some(&(x, y)) = stack.last() {
+        let mut dirs = [(0i32, -2i32), (2, 0), (0, 2), (-2, 0)];
+        dirs.shuffle(rng);
+        let next = dirs.iter().find_map(|&(dx, dy)| {
+            let (nx, ny) = (x as i32 + dx, y as i32 + dy);
+            let inside = nx > 0 && ny > 0 && (nx as usize) < w - 1 && (ny as usize) < h - 1;
+            (inside && !open[ny as usize][nx as usize]).then_some((nx as usize, ny as usize))
+        });
+        match next {
+            Some((nx, ny)) => {
+                open[(y + ny) / 2][(x + nx) / 2] = true; // pared intermedia
+                open[ny][nx] = true;
+                stack.push((nx, ny));
+            }
+            None => { stack.pop(); }
+        }
+    }
+    (1, 1)
+}
+
+/// Coloca habitaciones sin solaparse y las une en orden con pasillos en L
+fn carve_rooms(open: &mut [Vec<bool>], rng: &mut StdRng) -> (usize, usize) {
+    let (w, h) = (open[0].len(), open.len());
+    let mut rooms: Vec<(usize, usize, usize, usize)> = Vec::new(); // (x, y, ancho, alto)
+
+    for _ in 0..(w * h / 20) {
+        let rw = rng.gen_range(1..=((w - 3) / 2).min(4)) * 2 + 1;
+        let rh = rng.gen_range(1..=((h - 3) / 2).min(3)) * 2 + 1;
+        if rw + 2 > w || rh + 2 > h { continue; }
+        let rx = rng.gen_range(0..=(w - rw - 2) / 2) * 2 + 1;
+        let ry = rng.gen_range(0..=(h - rh - 2) / 2) * 2 + 1;
+        // Deja al menos una pared entre habitaciones
+        let overlaps = rooms.iter().any(|&(x, y, ow, oh)| {
+            rx <= x + ow && x <= rx + rw && ry <= y + oh && y <= ry + rh
+        });
+        if overlaps { continue; }
+        rooms.push((rx, ry, rw, rh));
+    }
+    if rooms.is_empty() {
+        rooms.push((1, 1, 1, 1));
+    }
+
+    for &(x, y, rw, rh) in &rooms {
+        for row in open.iter_mut().skip(y).take(rh) {
+            for cell in row.iter_mut().skip(x).take(rw) { *cell = true; }
+        }
+    }
+
+    // Pasillos entre centros de habitaciones consecutivas (de izquierda a derecha)
+    rooms.sort_by_key(|&(x, y, _, _)| (x, y));
+    let center = |&(x, y, rw, rh): &(usize, usize, usize, usize)| (x + rw / 2, y + rh / 2);
+    for pair in rooms.windows(2) {
+        let (ax, ay) = center(&pair[0]);
+        let (bx, by) = center(&pair[1]);
+        let corner = if rng.gen_bool(0.5) { (bx, ay) } else { (ax, by) };
+        for (fx, fy, tx, ty) in [(ax, ay, corner.0, corner.1), (corner.0, corner.1, bx, by)] {
+            open[fy][fx.min(tx)..=fx.max(tx)].fill(true);
+            for row in &mut open[fy.min(ty)..=fy.max(ty)] { row[fx] = true; }
+        }
+    }
+
+    center(&rooms[0])
+}
+
+/// Elige celdas para los objetos: deben dejar el resto del mapa conectado
+/// (los objetos bloquean el paso hasta recogerlos) y quedar lo más lejos
+/// posible del spawn y entre sí.
+fn place_items(open: &[Vec<bool>], spawn: (usize, usize), count: u32, rng: &mut StdRng) -> Vec<(usize, usize)> {
+    let (w, h) = (open[0].len(), open.len());
+    let mut blocked = vec![vec![false; w]; h];
+    // Distancia mínima de cada celda al spawn o a algún objeto ya puesto
+    let mut spread = bfs(open, &blocked, spawn);
+    let mut placed = Vec::new();
+
+    for _ in 0..count {
+        // Bloquear un punto de corte dejaría celdas aisladas
+        let cut = cut_cells(open, &blocked, spawn);
+        let mut candidates: Vec<(usize, usize, usize)> = Vec::new();
+        for (y, row) in spread.iter().enumerate() {
+            for (x, d) in row.iter().enumerate() {
+                if blocked[y][x] || cut[y][x] || (x, y) == spawn { continue; }
+                if let Some(d) = *d { candidates.push((x, y, d)); }
+            }
+        }
+        let Some(best) = candidates.iter().map(|c| c.2).max() else { break };
+        let top: Vec<&(usize, usize, usize)> = candidates.iter().filter(|c| c.2 == best).collect();
+        let &&(x, y, _) = top.choose(rng).unwrap();
+
+        blocked[y][x] = true;
+        placed.push((x, y));
+        let from_item = bfs(open, &vec![vec![false; w]; h], (x, y));
+        for (row, other) in spread.iter_mut().zip(from_item) {
+            for (d, o) in row.iter_mut().zip(other) {
+                *d = match (*d, o) {
+                    (Some(a), Some(b)) => Some(a.min(b)),
+                    (a, b) => a.or(b),
+                };
+            }
+        }
+    }
+    placed
+}
+
+/// Puntos de corte (Tarjan) de las celdas libres alcanzables desde `start`:
+/// las que, bloqueadas, separarían el mapa en partes. Las celdas que no se
+/// alcanzan también quedan marcadas, para no elegirlas. Sin recursión, para
+/// que los mapas grandes no agoten la pila.
+fn cut_cells(open: &[Vec<bool>], blocked: &[Vec<bool>], start: (usize, usize)) -> Vec<Vec<bool>> {
+    let (w, h) = (open[0].len(), open.len());
+    let free = |(x, y): (usize, usize)| open[y][x] && !blocked[y][x];
+    let mut order = vec![vec![None; w]; h]; // orden de descubrimiento
+    let mut low = vec![vec![0; w]; h];
+    let mut cut = vec![vec![true; w]; h];
+    let mut time = 0;
+    let mut root_children = 0;
+
+    order[start.1][start.0] = Some(time);
+    cut[start.1][start.0] = false;
+    // (celda, de dónde se llegó, próximo vecino a mirar)
+    let mut stack = vec![(start, start, 0)];
+    while let Some(&mut (cell, parent, ref mut next)) = stack.last_mut() {
+        if let Some(n) = neighbours(cell.0, cell.1, w, h).nth(*next) {
+            *next += 1;
+            if !free(n) || n == parent { continue; }
+            match order[n.1][n.0] {
+                Some(seen) => low[cell.1][cell.0] = low[cell.1][cell.0].min(seen),
+                None => {
+                    time += 1;
+                    order[n.1][n.0] = Some(time);
+                    low[n.1][n.0] = time;
+                    cut[n.1][n.0] = false;
+                    if cell == start { root_children += 1; }
+                    stack.push((n, cell, 0));
+                }
+            }
+        } else {
+            stack.pop();
+            if let Some(&(p, _, _)) = stack.last() {
+                low[p.1][p.0] = low[p.1][p.0].min(low[cell.1][cell.0]);
+                if p != start && low[cell.1][cell.0] >= order[p.1][p.0].unwrap_or(0) {
+                    cut[p.1][p.0] = true;
+                }
+            }
+        }
+    }
+    cut[start.1][start.0] = root_children > 1;
+    cut
+}
+
+fn bfs(open: &[Vec<bool>], blocked: &[Vec<bool>], start: (usize, usize)) -> Vec<Vec<Option<usize>>> {
+    let (w, h) = (open[0].len(), open.len());
+    let mut dist = vec![vec![None; w]; h];
+    dist[start.1][start.0] = Some(0);
+    let mut queue = VecDeque::from([start]);
+    while let Some((x, y)) = queue.pop_front() {
+        let d = dist[y][x].unwrap_or(0);
+        for (nx, ny) in neighbours(x, y, w, h) {
+            if open[ny][nx] && !blocked[ny][nx] && dist[ny][nx].is_none() {
+                dist[ny][nx] = Some(d + 1);
+                queue.push_back((nx, ny));
+            }
+        }
+    }
+    dist
+}
@@ -0,0 +1,123 @@
+use crate::audio::{AudioManager, Channel, SoundId, Spatial};
+use crate::framebuffer::Framebuffer;
+use crate::platform::{Key, Platform};
+use crate::settings::Video;
+use minifb::{KeyRepeat, MouseMode, Scale, Window, WindowOptions};
+
+/// Ventana de minifb con audio de rodio
+pub struct MinifbPlatform {
+    window: Window,
+    buffer: Vec<u32>,
+    pub audio: AudioManager,
+}
+
+impl MinifbPlatform {
+    /// Ventana del tamaño y la escala de `video`. `audio` puede ser
+    /// `AudioManager::null()` para jugar sin sonido.
+    pub fn new(title: &str, video: &Video, audio: AudioManager) -> Result<Self, minifb::Error> {
+        let options = if video.fullscreen {
+            // minifb no tiene pantalla completa: sin bordes y con la mayor escala que entre
+            WindowOptions { borderless: true, title: false, topmost: true, scale: Scale::FitScreen, ..WindowOptions::default() }
+        } else {
+            let scale = match video.scale {
+                2 => Scale::X2,
+                4 => Scale::X4,
+                8 => Scale::X8,
+                _ => Scale::X1,
+            };
+            WindowOptions { scale, ..WindowOptions::default() }
+        };
+        let window = Window::new(title, video.width, video.height, options)?;
+        Ok(MinifbPlatform { window, buffer: vec![0; video.width * video.height], audio })
+    }
+}
+
+fn to_minifb(key: Key) -> minifb::Key {
+    match key {
+        Key::Up => minifb::Key::Up,
+        Key::Down => minifb::Key::Down,
+        Key::Left => minifb::Key::Left,
+        Key::Right => minifb::Key::Right,
+        Key::W => minifb::Key::W,
+        Key::A => minifb::Key::A,
+        Key::S => minifb::Key::S,
+        Key::D => minifb::Key::D,
+        Key::E => minifb::Key::E,
+        Key::R => minifb::Key::R,
+        Key::X => minifb::Key::X,
+        Key::Space => minifb::Key::Space,
+        Key::LeftShift => minifb::Key::LeftShift,
+        Key::Enter => minifb::Key::Enter,
+        Key::Escape => minifb::Key::Escape,
+        Key::Key1 => minifb::Key::Key1,
+        Key::Key2 => minifb::Key::Key2,
+        Key::Key3 => minifb::Key::Key3,
+        Key::Key4 => minifb::Key::Key4,
+        Key::Key5 => minifb::Key::Key5,
+        Key::Q => minifb::Key::Q,
+        Key::F => minifb::Key::F,
+        Key::M => minifb::Key::M,
+        Key::Tab => minifb::Key::Tab,
+        Key::LeftCtrl => minifb::Key::LeftCtrl,
+    }
+}
+
+impl Platform for MinifbPlatform {
+    fn is_open(&self) -> bool {
+        self.window.is_open()
+    }
+
+    fn present(&mut self, framebuffer: &Framebuffer) {
+        self.buffer.resize(framebuffer.width * framebuffer.height, 0);
+        framebuffer.flush_to(&mut self.buffer);
+        self.audio.update();
+        self.window
+            .update_with_buffer(&self.buffer, framebuffer.width, framebuffer.height)
+            .unwrap();
+    }
+
+    fn is_key_down(&self, key: Key) -> bool {
+        self.window.is_key_down(to_minifb(key))
+    }
+
+    fn is_key_pressed(&self, key: Key) -> bool {
+        self.window.is_key_pressed(to_minifb(key), KeyRepeat::No)
+    }
+
+    // minifb no captura el puntero: es la posición absoluta del cursor
+    fn mouse_x(&self) -> Option<f32> {
+        self.window.get_mouse_pos(MouseMode::Discard).map(|(x, _)| x)
+    }
+
+    fn set_title(&mut self, title: &str) {
+        self.window.set_title(title);
+    }
+
+    fn play_sound_at(&mut self, id: SoundId, spatial: Spatial) {
+        self.audio.play_at(id, spatial);
+    }
+
+    fn set_loop(&mut self, id: SoundId, spatial: Option<Spatial>) {
+        self.audio.set_loop(id, spatial);
+    }
+
+    fn play_music(&mut self, tracks: &[String]) {
+        self.audio.play_music(tracks);
+    }
+
+    fn stop_music(&mut self) {
+        self.audio.stop_music();
+    }
+
+    fn volume(&self, channel: Channel) -> f32 {
+        self.audio.volume(channel)
+    }
+
+    fn set_volume(&mut self, channel: Channel, volume: f32) {
+        self.audio.set_volume(channel, volume);
+    }
+
+    fn duck_music(&mut self, level: f32) {
+        self.audio.duck_music(level);
+    }
+}
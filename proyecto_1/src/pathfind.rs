@@ -0,0 +1,56 @@
+use crate::door::{is_door, Doors};
+use crate::reach::neighbours;
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+
+/// ¿Se puede caminar por la celda? Libre o puerta abierta.
+pub fn walkable(maze: &[Vec<char>], doors: &Doors, x: usize, y: usize) -> bool {
+    let c = maze[y][x];
+    c == ' ' || (is_door(c) && doors.is_passable(x, y))
+}
+
+/// A* sobre la cuadrícula con vecinos ortogonales y heurística Manhattan.
+/// Devuelve las celdas desde `start` (excluida) hasta `goal` (incluida).
+pub fn astar(
+    maze: &[Vec<char>],
+    doors: &Doors,
+    start: (usize, usize),
+    goal: (usize, usize),
+) -> Option<Vec<(usize, usize)>> {
+    let h = maze.len();
+    let w = maze.first().map_or(0, |r| r.len());
+    if start.0 >= w || start.1 >= h || goal.0 >= w || goal.1 >= h { return None; }
+    if !walkable(maze, doors, goal.0, goal.1) { return None; }
+
+    let heuristic = |(x, y): (usize, usize)| x.abs_diff(goal.0) + y.abs_diff(goal.1);
+    let mut cost = vec![vec![usize::MAX; w]; h];
+    let mut came_from = vec![vec![None; w]; h];
+    let mut open = BinaryHeap::new();
+
+    cost[start.1][start.0] = 0;
+    open.push(Reverse((heuristic(start), start)));
+
+    while let Some(Reverse((_, current))) = open.pop() {
+        if current == goal {
+            let mut path = vec![current];
+            let mut node = current;
+            while let Some(prev) = came_from[node.1][node.0] {
+                if prev == start { break; }
+                path.push(prev);
+                node = prev;
+            }
+            path.reverse();
+            return Some(path);
+        }
+
+        let g = cost[current.1][current.0] + 1;
+        for (nx, ny) in neighbours(current.0, current.1, w, h) {
+            if g < cost[ny][nx] && walkable(maze, doors, nx, ny) {
+                cost[ny][nx] = g;
+                came_from[ny][nx] = Some(current);
+                open.push(Reverse((g + heuristic((nx, ny)), (nx, ny))));
+            }
+        }
+    }
+    None
+}
@@ -0,0 +1,336 @@
+use crate::audio::{AudioManager, Channel, SoundId, Spatial};
+use crate::framebuffer::Framebuffer;
+use std::collections::{HashMap, HashSet};
+
+/// Teclas que usa el juego, independientes de la biblioteca de ventanas
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Key {
+    Up,
+    Down,
+    Left,
+    Right,
+    W,
+    A,
+    S,
+    D,
+    E,
+    R,
+    X,
+    Space,
+    LeftShift,
+    Enter,
+    Escape,
+    Key1,
+    Key2,
+    Key3,
+    Key4,
+    Key5,
+    Q,
+    F,
+    M,
+    Tab,
+    LeftCtrl,
+}
+
+impl Key {
+    pub const ALL: [Key; 25] = [
+        Key::Up, Key::Down, Key::Left, Key::Right,
+        Key::W, Key::A, Key::S, Key::D, Key::E, Key::R, Key::X,
+        Key::Space, Key::LeftShift, Key::Enter, Key::Escape,
+        Key::Key1, Key::Key2, Key::Key3, Key::Key4, Key::Key5,
+        Key::Q, Key::F, Key::M, Key::Tab, Key::LeftCtrl,
+    ];
+
+    /// Nombre en los archivos de configuración
+    pub fn name(self) -> &'static str {
+        match self {
+            Key::Up => "Up",
+            Key::Down => "Down",
+            Key::Left => "Left",
+            Key::Right => "Right",
+            Key::W => "W",
+            Key::A => "A",
+            Key::S => "S",
+            Key::D => "D",
+            Key::E => "E",
+            Key::R => "R",
+            Key::X => "X",
+            Key::Space => "Space",
+            Key::LeftShift => "Shift",
+            Key::Enter => "Enter",
+            Key::Escape => "Escape",
+            Key::Key1 => "1",
+            Key::Key2 => "2",
+            Key::Key3 => "3",
+            Key::Key4 => "4",
+            Key::Key5 => "5",
+            Key::Q => "Q",
+            Key::F => "F",
+            Key::M => "M",
+            Key::Tab => "Tab",
+            Key::LeftCtrl => "Ctrl",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Key> {
+        Key::ALL.into_iter().find(|k| k.name().eq_ignore_ascii_case(name))
+    }
+
+    fn bit(self) -> u32 {
+        1 << self as u32
+    }
+}
+
+/// Foto del teclado para un paso de simulación: un bit por tecla
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Input {
+    pub down: u32,
+    pub pressed: u32,
+}
+
+impl Input {
+    pub fn read(platform: &dyn Platform) -> Self {
+        let mut input = Input::default();
+        for key in Key::ALL {
+            if platform.is_key_down(key) { input.down |= key.bit(); }
+            if platform.is_key_pressed(key) { input.pressed |= key.bit(); }
+        }
+        input
+    }
+
+    pub fn is_down(&self, key: Key) -> bool {
+        self.down & key.bit() != 0
+    }
+
+    pub fn is_pressed(&self, key: Key) -> bool {
+        self.pressed & key.bit() != 0
+    }
+
+    pub fn hold(&mut self, key: Key) {
+        self.down |= key.bit();
+    }
+
+    pub fn press(&mut self, key: Key) {
+        self.down |= key.bit();
+        self.pressed |= key.bit();
+    }
+}
+
+/// Lo que el juego necesita del sistema: mostrar cuadros, leer el teclado y
+/// reproducir sonido. Hay una implementación con ventana (`MinifbPlatform`, con
+/// la feature `window`) y otra sin ventana ni audio (`NullPlatform`) para
+/// pruebas y servidores.
+pub trait Platform {
+    /// Falso cuando el usuario cerró la ventana
+    fn is_open(&self) -> bool;
+    /// Muestra el cuadro y actualiza el estado del teclado
+    fn present(&mut self, framebuffer: &Framebuffer);
+    /// La tecla está apretada
+    fn is_key_down(&self, key: Key) -> bool;
+    /// La tecla se apretó desde el cuadro anterior (sin repetición)
+    fn is_key_pressed(&self, key: Key) -> bool;
+    /// Posición horizontal del mouse en píxeles; None si está fuera de la ventana
+    fn mouse_x(&self) -> Option<f32>;
+    fn set_title(&mut self, title: &str);
+    /// Efecto de sonido de una sola vez
+    fn play_sound(&mut self, id: SoundId) {
+        self.play_sound_at(id, Spatial::CENTER);
+    }
+    /// Efecto con volumen y paneo según de dónde viene
+    fn play_sound_at(&mut self, id: SoundId, spatial: Spatial);
+    /// Sonido en bucle que sigue al jugador; None lo detiene
+    fn set_loop(&mut self, id: SoundId, spatial: Option<Spatial>);
+    /// Lista de temas de fondo en bucle; si cambia, pasa a la nueva con un fundido
+    fn play_music(&mut self, tracks: &[String]);
+    fn stop_music(&mut self);
+    fn volume(&self, channel: Channel) -> f32;
+    /// Volumen elegido por el jugador (0.0 a 1.0)
+    fn set_volume(&mut self, channel: Channel, volume: f32);
+    /// Baja la música a `level` (1.0 = normal), p. ej. durante la pausa
+    fn duck_music(&mut self, level: f32);
+}
+
+/// Plataforma sin ventana ni audio. Las teclas se controlan desde el código
+/// y los sonidos pedidos quedan registrados para poder revisarlos.
+pub struct NullPlatform {
+    held: HashSet<Key>,
+    pressed: HashSet<Key>,
+    /// Cuadros presentados hasta ahora
+    pub frames: usize,
+    /// Si se define, la "ventana" se cierra al llegar a esa cantidad de cuadros
+    pub max_frames: Option<usize>,
+    pub title: String,
+    pub sounds: Vec<SoundId>,
+    /// Los efectos con posición, también anotados en `sounds`
+    pub spatial: Vec<(SoundId, Spatial)>,
+    pub loops: HashMap<SoundId, Spatial>,
+    /// Temas pedidos; vacío si no hay música
+    pub music: Vec<String>,
+    pub music_duck: f32,
+    /// Backend de audio nulo, guarda los volúmenes
+    pub audio: AudioManager,
+    /// Lo que devuelve `mouse_x`
+    pub mouse: Option<f32>,
+}
+
+impl Default for NullPlatform {
+    fn default() -> Self {
+        NullPlatform {
+            held: HashSet::new(),
+            pressed: HashSet::new(),
+            frames: 0,
+            max_frames: None,
+            title: String::new(),
+            sounds: Vec::new(),
+            spatial: Vec::new(),
+            loops: HashMap::new(),
+            music: Vec::new(),
+            music_duck: 1.0,
+            audio: AudioManager::null(),
+            mouse: None,
+        }
+    }
+}
+
+impl NullPlatform {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Mantiene la tecla apretada hasta `release`
+    pub fn hold(&mut self, key: Key) {
+        self.held.insert(key);
+    }
+
+    pub fn release(&mut self, key: Key) {
+        self.held.remove(&key);
+    }
+
+    /// Aprieta la tecla durante el cuadro actual (se suelta en el próximo `present`)
+    pub fn press(&mut self, key: Key) {
+        self.pressed.insert(key);
+    }
+}
+
+impl Platform for NullPlatform {
+    fn is_open(&self) -> bool {
+        self.max_frames.is_none_or(|max| self.frames < max)
+    }
+
+    fn present(&mut self, _framebuffer: &Framebuffer) {
+        self.frames += 1;
+        self.pressed.clear();
+    }
+
+    fn is_key_down(&self, key: Key) -> bool {
+        self.held.contains(&key) || self.pressed.contains(&key)
+    }
+
+    fn is_key_pressed(&self, key: Key) -> bool {
+        self.pressed.contains(&key)
+    }
+
+    fn mouse_x(&self) -> Option<f32> {
+        self.mouse
+    }
+
+    fn set_title(&mut self, title: &str) {
+        self.title = title.to_string();
+    }
+
+    fn play_sound_at(&mut self, id: SoundId, spatial: Spatial) {
+        self.sounds.push(id);
+        if spatial != Spatial::CENTER {
+            self.spatial.push((id, spatial));
+        }
+    }
+
+    fn set_loop(&mut self, id: SoundId, spatial: Option<Spatial>) {
+        match spatial {
+            Some(spatial) => self.loops.insert(id, spatial),
+            None => self.loops.remove(&id),
+        };
+    }
+
+    fn play_music(&mut self, tracks: &[String]) {
+        self.music = tracks.to_vec();
+    }
+
+    fn stop_music(&mut self) {
+        self.music.clear();
+    }
+
+    fn volume(&self, channel: Channel) -> f32 {
+        self.audio.volume(channel)
+    }
+
+    fn set_volume(&mut self, channel: Channel, volume: f32) {
+        self.audio.set_volume(channel, volume);
+    }
+
+    fn duck_music(&mut self, level: f32) {
+        self.music_duck = level;
+    }
+}
+
+/// Responde al teclado con un `Input` fijo y delega todo lo demás.
+/// Así cada paso de simulación ve las mismas teclas sin importar cuántos
+/// pasos entren en un cuadro.
+pub struct FixedInput<'a> {
+    pub platform: &'a mut dyn Platform,
+    pub input: Input,
+}
+
+impl Platform for FixedInput<'_> {
+    fn is_open(&self) -> bool {
+        self.platform.is_open()
+    }
+
+    fn present(&mut self, framebuffer: &Framebuffer) {
+        self.platform.present(framebuffer);
+    }
+
+    fn is_key_down(&self, key: Key) -> bool {
+        self.input.is_down(key)
+    }
+
+    fn is_key_pressed(&self, key: Key) -> bool {
+        self.input.is_pressed(key)
+    }
+
+    fn mouse_x(&self) -> Option<f32> {
+        self.platform.mouse_x()
+    }
+
+    fn set_title(&mut self, title: &str) {
+        self.platform.set_title(title);
+    }
+
+    fn play_sound_at(&mut self, id: SoundId, spatial: Spatial) {
+        self.platform.play_sound_at(id, spatial);
+    }
+
+    fn set_loop(&mut self, id: SoundId, spatial: Option<Spatial>) {
+        self.platform.set_loop(id, spatial);
+    }
+
+    fn play_music(&mut self, tracks: &[String]) {
+        self.platform.play_music(tracks);
+    }
+
+    fn stop_music(&mut self) {
+        self.platform.stop_music();
+    }
+
+    fn volume(&self, channel: Channel) -> f32 {
+        self.platform.volume(channel)
+    }
+
+    fn set_volume(&mut self, channel: Channel, volume: f32) {
+        self.platform.set_volume(channel, volume);
+    }
+
+    fn duck_music(&mut self, level: f32) {
+        self.platform.duck_music(level);
+    }
+}
@@ -1,11 +1,25 @@
+use crate::door::{is_door, Doors};
+use crate::inventory::{Inventory, KeyColor, AMMO_TILE, ARMOUR_TILE};
+
 pub struct Player {
     pub pos: Position,
     pub a: f32,     // ángulo (rad)
     pub fov: f32,   // campo de visión (rad)
     pub radius: f32, // radio de colisión (en unidades del mundo)
     pub collected_items: u32, // Nuevo: contador de objetos recolectados
+    pub inventory: Inventory, // llaves y objetos que lleva encima
+    pub health: i32,
+    pub armour: i32, // absorbe la mitad del daño mientras dure
+    pub ammo: u32,
 }
 
+pub const MAX_HEALTH: i32 = 100;
+pub const MAX_ARMOUR: i32 = 100;
+pub const START_AMMO: u32 = 30;
+/// Lo que da cada chaleco y cada caja de balas
+pub const ARMOUR_PICKUP: i32 = 50;
+pub const AMMO_PICKUP: u32 = 10;
+
 pub struct Position {
     pub x: f32,
     pub y: f32,
@@ -19,33 +33,43 @@ impl Player {
             fov,
             radius: 10.0, // ajusta al gusto
             collected_items: 0, // Inicializar en 0
+            inventory: Inventory::new(),
+            health: MAX_HEALTH,
+            armour: 0,
+            ammo: START_AMMO,
         }
     }
 
     pub fn rotate_left(&mut self, angle: f32) { self.a -= angle; }
     pub fn rotate_right(&mut self, angle: f32) { self.a += angle; }
 
-    pub fn move_forward(&mut self, step: f32, maze: &Vec<Vec<char>>, block: usize) {
+    pub fn move_forward(&mut self, step: f32, maze: &[Vec<char>], doors: &Doors, block: usize) {
         let dx = self.a.cos() * step;
         let dy = self.a.sin() * step;
-        self.try_move(dx, dy, maze, block);
+        self.try_move(dx, dy, maze, doors, block);
     }
 
-    pub fn move_backward(&mut self, step: f32, maze: &Vec<Vec<char>>, block: usize) {
+    pub fn move_backward(&mut self, step: f32, maze: &[Vec<char>], doors: &Doors, block: usize) {
         let dx = -self.a.cos() * step;
         let dy = -self.a.sin() * step;
-        self.try_move(dx, dy, maze, block);
+        self.try_move(dx, dy, maze, doors, block);
+    }
+
+    /// Paso lateral; positivo hacia la derecha
+    pub fn strafe(&mut self, step: f32, maze: &[Vec<char>], doors: &Doors, block: usize) {
+        let dx = -self.a.sin() * step;
+        let dy = self.a.cos() * step;
+        self.try_move(dx, dy, maze, doors, block);
     }
-    
 
     /// Movimiento con "deslizamiento": prueba eje X y eje Y por separado
-    fn try_move(&mut self, dx: f32, dy: f32, maze: &Vec<Vec<char>>, block: usize) {
+    fn try_move(&mut self, dx: f32, dy: f32, maze: &[Vec<char>], doors: &Doors, block: usize) {
         let nx = self.pos.x + dx;
-        if !collides(maze, nx, self.pos.y, self.radius, block) {
+        if !collides(maze, doors, nx, self.pos.y, self.radius, block) {
             self.pos.x = nx;
         }
         let ny = self.pos.y + dy;
-        if !collides(maze, self.pos.x, ny, self.radius, block) {
+        if !collides(maze, doors, self.pos.x, ny, self.radius, block) {
             self.pos.y = ny;
         }
     }
@@ -55,6 +79,32 @@ impl Player {
         self.collected_items += 1;
     }
 
+    /// Recoge lo que hay en la celda: las llaves van al inventario, la
+    /// armadura y la munición se suman y el resto cuenta como objetivo del nivel
+    pub fn pick_up(&mut self, tile: char) {
+        if let Some(color) = KeyColor::from_key_tile(tile) {
+            self.inventory.add_key(color);
+        } else if tile == ARMOUR_TILE {
+            self.armour = (self.armour + ARMOUR_PICKUP).min(MAX_ARMOUR);
+        } else if tile == AMMO_TILE {
+            self.ammo += AMMO_PICKUP;
+        } else {
+            self.collect_item();
+            self.inventory.add_item(tile);
+        }
+    }
+
+    /// Recibe daño; la armadura se gasta primero y absorbe la mitad
+    pub fn take_damage(&mut self, amount: i32) {
+        let absorbed = (amount / 2).min(self.armour);
+        self.armour -= absorbed;
+        self.health = (self.health - (amount - absorbed)).max(0);
+    }
+
+    pub fn is_dead(&self) -> bool {
+        self.health <= 0
+    }
+
     pub fn has_all_items(&self, total_items: u32) -> bool {
         self.collected_items >= total_items
     }
@@ -66,18 +116,20 @@ impl Player {
 }
 
 /// ¿El círculo del jugador colisiona con alguna pared?
-fn collides(maze: &Vec<Vec<char>>, x: f32, y: f32, r: f32, block: usize) -> bool {
+/// Los enemigos usan la misma regla.
+pub fn collides(maze: &[Vec<char>], doors: &Doors, x: f32, y: f32, r: f32, block: usize) -> bool {
     // chequea las 4 esquinas del bounding box del círculo
-    is_wall_cell(maze, x - r, y - r, block) ||
-    is_wall_cell(maze, x + r, y - r, block) ||
-    is_wall_cell(maze, x - r, y + r, block) ||
-    is_wall_cell(maze, x + r, y + r, block)
+    is_wall_cell(maze, doors, x - r, y - r, block) ||
+    is_wall_cell(maze, doors, x + r, y - r, block) ||
+    is_wall_cell(maze, doors, x - r, y + r, block) ||
+    is_wall_cell(maze, doors, x + r, y + r, block)
 }
 
-fn is_wall_cell(maze: &Vec<Vec<char>>, x: f32, y: f32, block: usize) -> bool {
+fn is_wall_cell(maze: &[Vec<char>], doors: &Doors, x: f32, y: f32, block: usize) -> bool {
     if x < 0.0 || y < 0.0 { return true; }
     let i = (x as usize) / block;
     let j = (y as usize) / block;
     if j >= maze.len() || i >= maze[0].len() { return true; }
+    if is_door(maze[j][i]) { return !doors.is_passable(i, j); } // solo bloquea cerrada
     maze[j][i] != ' ' // cualquier char != espacio es pared
 }
\ No newline at end of file
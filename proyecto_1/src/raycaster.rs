@@ -1,180 +1,408 @@
+use crate::door::{is_door, Doors};
 use crate::framebuffer::Framebuffer;
+use crate::inventory::is_pickup;
+use crate::map::load_map;
 use crate::player::Player;
 use crate::textures::Textures;
 
+/// Cara de la celda contra la que chocó el rayo
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Side {
+    North,
+    South,
+    East,
+    West,
+}
+
 pub struct Intersect {
     pub distance: f32,
     pub impact: char,
     pub object_type: Option<char>, // Para identificar objetos
     pub hit_x: f32,
     pub hit_y: f32,
+    pub side: Side,     // cara golpeada
+    pub cell_x: usize,  // columna de la celda golpeada
+    pub cell_y: usize,  // fila de la celda golpeada
+    pub wall_x: f32,    // coordenada de textura (0..1) a lo largo de la cara
 }
 
+/// Lanza un rayo recorriendo la cuadrícula con DDA: avanza de borde en borde
+/// de celda, así el punto de impacto es exacto y no depende de un paso fijo.
+/// Se detiene en el primer objeto o pared.
 pub fn cast_ray(
-    maze: &Vec<Vec<char>>,
+    maze: &[Vec<char>],
+    doors: &Doors,
     player: &Player,
     a: f32,
     block_size: usize,
 ) -> Intersect {
-    let mut d = 0.0f32;
-    let step = 1.0f32;
+    traverse(maze, doors, player.pos.x, player.pos.y, a, block_size, true)
+}
 
-    loop {
-        let x = player.pos.x + a.cos() * d;
-        let y = player.pos.y + a.sin() * d;
+/// Rayo desde cualquier punto (x, y) que atraviesa los objetos y solo se detiene
+/// en paredes y puertas cerradas. Lo usa el render (los objetos se dibujan después
+/// como sprites) y la línea de visión de los enemigos.
+pub fn cast_ray_from(
+    maze: &[Vec<char>],
+    doors: &Doors,
+    x: f32,
+    y: f32,
+    a: f32,
+    block_size: usize,
+) -> Intersect {
+    traverse(maze, doors, x, y, a, block_size, false)
+}
 
-        if x < 0.0 || y < 0.0 { 
-            return Intersect { 
-                distance: d, 
+fn traverse(
+    maze: &[Vec<char>],
+    doors: &Doors,
+    ox: f32,
+    oy: f32,
+    a: f32,
+    block_size: usize,
+    stop_at_objects: bool,
+) -> Intersect {
+    let bs = block_size as f32;
+    let dir_x = a.cos();
+    let dir_y = a.sin();
+
+    let mut map_x = (ox / bs).floor() as i32;
+    let mut map_y = (oy / bs).floor() as i32;
+
+    // Distancia que recorre el rayo para cruzar una celda completa en cada eje
+    let delta_x = if dir_x == 0.0 { f32::INFINITY } else { (bs / dir_x).abs() };
+    let delta_y = if dir_y == 0.0 { f32::INFINITY } else { (bs / dir_y).abs() };
+
+    // Dirección del paso y distancia hasta el primer borde en cada eje
+    let (step_x, mut side_x) = if dir_x < 0.0 {
+        (-1, (ox - map_x as f32 * bs) / -dir_x)
+    } else {
+        (1, ((map_x + 1) as f32 * bs - ox) / dir_x)
+    };
+    let (step_y, mut side_y) = if dir_y < 0.0 {
+        (-1, (oy - map_y as f32 * bs) / -dir_y)
+    } else {
+        (1, ((map_y + 1) as f32 * bs - oy) / dir_y)
+    };
+
+    loop {
+        // Avanzar al borde más cercano
+        let (d, side) = if side_x < side_y {
+            let d = side_x;
+            side_x += delta_x;
+            map_x += step_x;
+            (d, if step_x > 0 { Side::West } else { Side::East })
+        } else {
+            let d = side_y;
+            side_y += delta_y;
+            map_y += step_y;
+            (d, if step_y > 0 { Side::North } else { Side::South })
+        };
+
+        let hit_x = ox + dir_x * d;
+        let hit_y = oy + dir_y * d;
+
+        // Coordenada de textura: las caras N/S avanzan en x, las E/O en y.
+        // Se invierte en N y E para que la textura no se vea espejada.
+        let wall_x = match side {
+            Side::South => (hit_x / bs).fract(),
+            Side::North => 1.0 - (hit_x / bs).fract(),
+            Side::West => (hit_y / bs).fract(),
+            Side::East => 1.0 - (hit_y / bs).fract(),
+        };
+
+        // Fuera del mapa: se trata como pared
+        if map_x < 0 || map_y < 0
+            || map_y as usize >= maze.len() || map_x as usize >= maze[0].len() {
+            return Intersect {
+                distance: d,
                 impact: '#',
                 object_type: None,
-                hit_x: x,
-                hit_y: y,
-            }; 
+                hit_x,
+                hit_y,
+                side,
+                cell_x: map_x.max(0) as usize,
+                cell_y: map_y.max(0) as usize,
+                wall_x,
+            };
         }
 
-        let i = (x as usize) / block_size;
-        let j = (y as usize) / block_size;
+        let (i, j) = (map_x as usize, map_y as usize);
+        let cell = maze[j][i];
 
-        if j >= maze.len() || i >= maze[0].len() { 
-            return Intersect { 
-                distance: d, 
-                impact: '#',
-                object_type: None,
-                hit_x: x,
-                hit_y: y,
-            }; 
+        // Puertas: el panel está hundido media celda y se desliza a lo largo de ella.
+        // Se intersecta con el plano central; si el rayo pasa por el hueco abierto sigue.
+        if is_door(cell) {
+            let exit = side_x.min(side_y); // distancia a la que el rayo sale de la celda
+            let horizontal = doors.is_horizontal(i, j);
+            let (dir, origin, mid) = if horizontal {
+                (dir_y, oy, (j as f32 + 0.5) * bs)
+            } else {
+                (dir_x, ox, (i as f32 + 0.5) * bs)
+            };
+            if dir != 0.0 {
+                let t = (mid - origin) / dir;
+                if t >= d && t <= exit {
+                    let (px, py) = (ox + dir_x * t, oy + dir_y * t);
+                    let along = if horizontal { px / bs } else { py / bs };
+                    let open = doors.openness(i, j);
+                    if along.fract() >= open {
+                        let side = match (horizontal, dir > 0.0) {
+                            (true, true) => Side::North,
+                            (true, false) => Side::South,
+                            (false, true) => Side::West,
+                            (false, false) => Side::East,
+                        };
+                        return Intersect {
+                            distance: t,
+                            impact: cell,
+                            object_type: None,
+                            hit_x: px,
+                            hit_y: py,
+                            side,
+                            cell_x: i,
+                            cell_y: j,
+                            wall_x: along.fract() - open,
+                        };
+                    }
+                }
+            }
+            continue;
         }
 
-        let cell = maze[j][i];
-        
-        // Detectar objetos (1, 2, 3)
-        if cell == '1' || cell == '2' || cell == '3' {
-            return Intersect { 
-                distance: d, 
+        // Detectar objetos (1, 2, 3) y llaves
+        if is_pickup(cell) {
+            if !stop_at_objects { continue; }
+            return Intersect {
+                distance: d,
                 impact: ' ', // No es una pared
                 object_type: Some(cell),
-                hit_x: x,
-                hit_y: y,
+                hit_x,
+                hit_y,
+                side,
+                cell_x: i,
+                cell_y: j,
+                wall_x,
             };
         }
-        
-        if cell != ' ' { 
-            return Intersect { 
-                distance: d, 
+
+        if cell != ' ' {
+            return Intersect {
+                distance: d,
                 impact: cell,
                 object_type: None,
-                hit_x: x,
-                hit_y: y,
-            }; 
+                hit_x,
+                hit_y,
+                side,
+                cell_x: i,
+                cell_y: j,
+                wall_x,
+            };
         }
+    }
+}
 
-        d += step;
-        if d > 5000.0 { 
-            return Intersect { 
-                distance: d, 
-                impact: ' ',
-                object_type: None,
-                hit_x: x,
-                hit_y: y,
-            }; 
+/// Sprite suelto en el mundo (p. ej. un enemigo), además de los objetos del mapa
+pub struct Sprite {
+    pub x: f32,
+    pub y: f32,
+    pub texture: char,
+}
+
+/// Textura de piso por defecto cuando la celda no define una
+pub const DEFAULT_FLOOR: char = 'B';
+/// Textura de techo por defecto cuando la celda no define una
+pub const DEFAULT_CEILING: char = '#';
+
+/// Texturas de piso y techo por celda (mismas dimensiones que el laberinto).
+/// Una celda con ' ' usa la textura por defecto del nivel.
+#[derive(Clone)]
+pub struct Surfaces {
+    pub floor: Vec<Vec<char>>,
+    pub ceiling: Vec<Vec<char>>,
+    pub default_floor: char,
+    pub default_ceiling: char,
+}
+
+impl Surfaces {
+    /// Piso y techo uniformes en todo el nivel
+    pub fn uniform(floor: char, ceiling: char) -> Self {
+        Self {
+            floor: Vec::new(),
+            ceiling: Vec::new(),
+            default_floor: floor,
+            default_ceiling: ceiling,
         }
     }
+
+    /// Carga las capas opcionales junto al mapa: para "maze1.txt" busca
+    /// "maze1.floor.txt" y "maze1.ceiling.txt". Si no existen se usa el valor por defecto.
+    pub fn load(maze_path: &str) -> Self {
+        let base = std::path::Path::new(maze_path).with_extension("");
+        let layer = |suffix: &str| {
+            let path = format!("{}.{}.txt", base.display(), suffix);
+            load_map(&path).unwrap_or_default()
+        };
+        Self {
+            floor: layer("floor"),
+            ceiling: layer("ceiling"),
+            default_floor: DEFAULT_FLOOR,
+            default_ceiling: DEFAULT_CEILING,
+        }
+    }
+
+    pub fn floor_at(&self, i: usize, j: usize) -> char {
+        layer_at(&self.floor, i, j).unwrap_or(self.default_floor)
+    }
+
+    pub fn ceiling_at(&self, i: usize, j: usize) -> char {
+        layer_at(&self.ceiling, i, j).unwrap_or(self.default_ceiling)
+    }
 }
 
-pub fn render3d(
-    framebuffer: &mut Framebuffer,
-    player: &Player,
-    maze: &Vec<Vec<char>>,
-    block_size: usize,
-    textures: &Textures
-) {
+fn layer_at(layer: &[Vec<char>], i: usize, j: usize) -> Option<char> {
+    match layer.get(j).and_then(|row| row.get(i)) {
+        Some(&c) if c != ' ' => Some(c),
+        _ => None,
+    }
+}
+
+/// Lo que se ve desde la cámara: mapa, puertas, piso y techo, texturas y sprites
+#[derive(Clone, Copy)]
+pub struct Scene<'a> {
+    pub maze: &'a [Vec<char>],
+    pub doors: &'a Doors,
+    pub surfaces: &'a Surfaces,
+    pub textures: &'a Textures,
+    pub sprites: &'a [Sprite],
+}
+
+pub fn render3d(framebuffer: &mut Framebuffer, player: &Player, scene: &Scene<'_>, block_size: usize) {
+    let Scene { maze, doors, surfaces, textures, sprites } = *scene;
     let w = framebuffer.width;
     let h = framebuffer.height;
     let hh = h as f32 / 2.0;
+    let bs = block_size as f32;
 
-    // cielo y piso
-    for y in 0..(h / 2) { for x in 0..w { framebuffer.point(x, y, 0x303050); } }
-    for y in (h / 2)..h { for x in 0..w { framebuffer.point(x, y, 0x202020); } }
+    // Profundidad de la pared en cada columna, para ocultar sprites detrás
+    let mut zbuffer = vec![f32::INFINITY; w];
 
-    // Renderizar paredes
-    for x in 0..w {
+    // Renderizar paredes, piso y techo columna por columna
+    for (x, depth) in zbuffer.iter_mut().enumerate() {
         let t = x as f32 / w as f32;
         let a = player.a - (player.fov / 2.0) + player.fov * t;
 
-        let hit = cast_ray(maze, player, a, block_size);
-        
-        if hit.object_type.is_some() {
-            continue; // los objetos se renderizan después
-        }
+        let hit = cast_ray_from(maze, doors, player.pos.x, player.pos.y, a, block_size);
 
         let distance = hit.distance * (player.a - a).cos();
-        if distance <= 0.0 { continue; }
-
-        let stake_h = (block_size as f32 * hh) / distance;
-        let top = (hh - stake_h / 2.0).max(0.0) as usize;
-        let bot = (hh + stake_h / 2.0).min((h - 1) as f32) as usize;
-
-        let wall_x = hit.hit_x % block_size as f32 / block_size as f32;
+        *depth = distance;
+
+        // Tramo vertical ocupado por la pared (None si no hay pared que dibujar)
+        let mut wall = None;
+        if distance > 0.0 {
+            let stake_h = (bs * hh) / distance;
+            let top = (hh - stake_h / 2.0).max(0.0) as usize;
+            let bot = (hh + stake_h / 2.0).min((h - 1) as f32) as usize;
+
+            for y in top..=bot {
+                let v = (y - top) as f32 / (bot - top + 1) as f32;
+                let color = textures.sample(hit.impact, hit.wall_x, v);
+                framebuffer.point(x, y, color);
+            }
+            wall = Some((top, bot));
+        }
 
-        for y in top..=bot {
-            let v = (y - top) as f32 / (bot - top + 1) as f32;
-            let color = textures.sample(hit.impact, wall_x, v);
-            framebuffer.point(x, y, color);
+        // Piso y techo: para cada fila bajo el horizonte se calcula la distancia
+        // al suelo y el punto del mundo que ve el rayo; el techo es su espejo.
+        let (ray_cos, ray_sin) = (a.cos(), a.sin());
+        let fisheye = (a - player.a).cos();
+        for y in (h / 2)..h {
+            let ceil_y = h - 1 - y;
+            let draw_floor = wall.is_none_or(|(_, bot)| y > bot);
+            let draw_ceiling = wall.is_none_or(|(top, _)| ceil_y < top);
+            if !draw_floor && !draw_ceiling { continue; }
+
+            let row_dist = bs * hh / (2.0 * (y as f32 + 0.5 - hh)) / fisheye;
+            let wx = player.pos.x + ray_cos * row_dist;
+            let wy = player.pos.y + ray_sin * row_dist;
+            if wx < 0.0 || wy < 0.0 { continue; }
+
+            let (i, j) = ((wx / bs) as usize, (wy / bs) as usize);
+            let (u, v) = ((wx / bs).fract(), (wy / bs).fract());
+
+            if draw_floor {
+                framebuffer.point(x, y, textures.sample(surfaces.floor_at(i, j), u, v));
+            }
+            if draw_ceiling {
+                framebuffer.point(x, ceil_y, textures.sample(surfaces.ceiling_at(i, j), u, v));
+            }
         }
     }
 
     // Renderizar sprites/objetos
-    render_sprites(framebuffer, player, maze, block_size, textures);
-
-    // Renderizar minimapa
-    render_minimap(framebuffer, player, maze, 10, 10, 4, block_size);
+    render_sprites(framebuffer, player, maze, block_size, textures, sprites, &zbuffer);
 }
 
 fn render_sprites(
     fb: &mut Framebuffer,
     player: &Player,
-    maze: &Vec<Vec<char>>,
+    maze: &[Vec<char>],
     block_size: usize,
     textures: &Textures,
+    extra: &[Sprite],
+    zbuffer: &[f32],
 ) {
     let w = fb.width;
     let h = fb.height;
     let hh = h as f32 / 2.0;
 
-    for (y, row) in maze.iter().enumerate() {
-        for (x, &cell) in row.iter().enumerate() {
-            if cell != '1' && cell != '2' && cell != '3' { continue; }
-
-            let obj_x = (x * block_size) as f32 + block_size as f32 / 2.0;
-            let obj_y = (y * block_size) as f32 + block_size as f32 / 2.0;
-
-            let dx = obj_x - player.pos.x;
-            let dy = obj_y - player.pos.y;
-            let dist = (dx*dx + dy*dy).sqrt();
-            let mut angle = dy.atan2(dx) - player.a;
-
-            while angle > std::f32::consts::PI { angle -= 2.0 * std::f32::consts::PI; }
-            while angle < -std::f32::consts::PI { angle += 2.0 * std::f32::consts::PI; }
-
-            if angle.abs() < player.fov / 2.0 && dist > 0.5 {
-                let screen_x = (0.5 + angle / player.fov) * w as f32;
-                let sprite_size = (block_size as f32 * hh / dist) as usize;
-                let half_size = sprite_size / 2;
-
-                let start_x = (screen_x as isize - half_size as isize).max(0) as usize;
-                let end_x = (screen_x as isize + half_size as isize).min(w as isize - 1) as usize;
-                let start_y = (hh as isize - half_size as isize).max(0) as usize;
-                let end_y = (hh as isize + half_size as isize).min(h as isize - 1) as usize;
-
-                for sy in start_y..end_y {
-                    for sx in start_x..end_x {
-                        let u = (sx as f32 - (screen_x - half_size as f32)) / sprite_size as f32;
-                        let v = (sy as f32 - (hh - half_size as f32)) / sprite_size as f32;
-                        let color = textures.sample_sprite(cell, u, v);
-                        if color != 0x000000 { fb.point(sx, sy, color); }
-                    }
-                }
+    // Objetos del mapa (en el centro de su celda) más los sprites sueltos
+    let half = block_size as f32 / 2.0;
+    let from_maze = maze.iter().enumerate().flat_map(|(y, row)| {
+        row.iter().enumerate()
+            .filter(|(_, cell)| is_pickup(**cell))
+            .map(move |(x, &cell)| ((x * block_size) as f32 + half, (y * block_size) as f32 + half, cell))
+    });
+    let from_world = extra.iter().map(|s| (s.x, s.y, s.texture));
+
+    // Reunir los sprites visibles con su distancia perpendicular a la cámara
+    let mut sprites: Vec<(f32, f32, char)> = Vec::new(); // (profundidad, screen_x, textura)
+    for (obj_x, obj_y, cell) in from_maze.chain(from_world) {
+        let dx = obj_x - player.pos.x;
+        let dy = obj_y - player.pos.y;
+        let dist = (dx*dx + dy*dy).sqrt();
+        let mut angle = dy.atan2(dx) - player.a;
+
+        while angle > std::f32::consts::PI { angle -= 2.0 * std::f32::consts::PI; }
+        while angle < -std::f32::consts::PI { angle += 2.0 * std::f32::consts::PI; }
+
+        if angle.abs() < player.fov / 2.0 && dist > 0.5 {
+            let screen_x = (0.5 + angle / player.fov) * w as f32;
+            sprites.push((dist * angle.cos(), screen_x, cell));
+        }
+    }
+
+    // Pintar de atrás hacia adelante para que los cercanos tapen a los lejanos
+    sprites.sort_by(|a, b| b.0.total_cmp(&a.0));
+
+    for (depth, screen_x, cell) in sprites {
+        let sprite_size = (block_size as f32 * hh / depth) as usize;
+        let half_size = sprite_size / 2;
+
+        let start_x = (screen_x as isize - half_size as isize).max(0) as usize;
+        let end_x = (screen_x as isize + half_size as isize).min(w as isize - 1) as usize;
+        let start_y = (hh as isize - half_size as isize).max(0) as usize;
+        let end_y = (hh as isize + half_size as isize).min(h as isize - 1) as usize;
+
+        // take/skip y no un slice: si el sprite queda fuera de pantalla, start_x > end_x
+        for (sx, &wall_depth) in zbuffer.iter().enumerate().take(end_x).skip(start_x) {
+            // La pared de esta columna está más cerca que el sprite
+            if wall_depth < depth { continue; }
+            for sy in start_y..end_y {
+                let u = (sx as f32 - (screen_x - half_size as f32)) / sprite_size as f32;
+                let v = (sy as f32 - (hh - half_size as f32)) / sprite_size as f32;
+                let color = textures.sample_sprite(cell, u, v);
+                if color != 0x000000 { fb.point(sx, sy, color); }
             }
         }
     }
@@ -183,7 +411,7 @@ fn render_sprites(
 pub fn render_minimap(
     fb: &mut Framebuffer,
     player: &Player,
-    maze: &Vec<Vec<char>>,
+    maze: &[Vec<char>],
     x_off: usize,
     y_off: usize,
     scale: usize,
@@ -195,6 +423,12 @@ pub fn render_minimap(
             'A' => 0xCC3333,
             'B' => 0x33CC33,
             'C' => 0x3333CC,
+            'D' | 'd' => 0xAA7744,
+            'R' | 'r' => 0xDC2828,
+            'G' | 'g' => 0x28C846,
+            'Y' | 'y' => 0xF0D228,
+            'a' => 0x468C46,
+            'm' => 0xB48C3C,
             '1' => 0xFF0000,
             '2' => 0x00FF00,
             '3' => 0x0000FF,
@@ -258,14 +492,3 @@ fn draw_line(fb: &mut Framebuffer, x0: i32, y0: i32, x1: i32, y1: i32, color: u3
         if e2 <= dx { err += dx; y0 += sy; }
     }
 }
-
-pub fn load_maze(path: &str) -> Vec<Vec<char>> {
-    use std::fs::read_to_string;
-    let contents = read_to_string(path).expect("No se pudo leer el archivo");
-    let mut lines: Vec<Vec<char>> = contents.lines().map(|l| l.chars().collect()).collect();
-    let max_w = lines.iter().map(|r| r.len()).max().unwrap_or(0);
-    for r in lines.iter_mut() {
-        if r.len() < max_w { r.resize(max_w, ' '); }
-    }
-    lines
-}
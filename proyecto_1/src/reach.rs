@@ -0,0 +1,189 @@
+use crate::door::is_door;
+use crate::inventory::{is_pickup, KeyColor};
+use std::collections::VecDeque;
+
+/// Resultado del análisis de alcance de un mapa desde el spawn
+pub struct ReachReport {
+    /// Celdas libres que el jugador puede pisar
+    pub reachable: Vec<Vec<bool>>,
+    /// Cada objeto o llave del mapa con su distancia a pie
+    pub objectives: Vec<Objective>,
+    /// Regiones libres (conectadas entre sí) a las que no se puede llegar
+    pub dead_zones: Vec<Vec<(usize, usize)>>,
+}
+
+pub struct Objective {
+    pub col: usize,
+    pub row: usize,
+    pub tile: char,
+    /// Celdas a recorrer hasta poder recogerlo (None si no se alcanza)
+    pub steps: Option<usize>,
+    /// Lo mismo en unidades del mundo
+    pub distance: Option<f32>,
+}
+
+impl ReachReport {
+    pub fn unreachable(&self) -> impl Iterator<Item = &Objective> {
+        self.objectives.iter().filter(|o| o.steps.is_none())
+    }
+}
+
+/// Analiza qué partes del mapa se alcanzan desde (x, y) en unidades del mundo.
+///
+/// Usa la misma regla que `player::collides`: la caja del círculo de radio
+/// `radius` debe quedar sobre celdas libres. Con un radio menor a media celda
+/// basta una celda libre; con radios mayores el jugador necesita una ventana de
+/// n×n celdas libres para pasar. Las puertas cuentan como libres, las puertas
+/// con llave solo si la llave se puede recoger antes, y los objetos dejan de
+/// estorbar una vez recogidos.
+pub fn analyze(maze: &[Vec<char>], x: f32, y: f32, radius: f32, block_size: usize) -> ReachReport {
+    let h = maze.len();
+    let w = maze.first().map_or(0, |r| r.len());
+    let bs = block_size as f32;
+    // Celdas que ocupa la caja del jugador en cada eje
+    let n = (2.0 * radius / bs).floor() as usize + 1;
+
+    let start = (
+        ((x - radius).max(0.0) / bs) as usize,
+        ((y - radius).max(0.0) / bs) as usize,
+    );
+
+    // Lo que se recoge deja la celda libre y las llaves abren sus puertas,
+    // así que se repite la inundación mientras se alcancen objetos nuevos
+    let mut keys: Vec<KeyColor> = Vec::new();
+    let mut collected = vec![vec![false; w]; h];
+    let mut dist = flood(maze, w, h, n, start, &keys, &collected);
+    loop {
+        let found: Vec<(usize, usize, char)> = pickups(maze)
+            .filter(|&(col, row, _)| !collected[row][col] && pickup_steps(&dist, n, col, row).is_some())
+            .collect();
+        if found.is_empty() { break; }
+        for (col, row, tile) in found {
+            collected[row][col] = true;
+            keys.extend(KeyColor::from_key_tile(tile));
+        }
+        dist = flood(maze, w, h, n, start, &keys, &collected);
+    }
+
+    // Celdas cubiertas por alguna ventana alcanzada
+    let mut reachable = vec![vec![false; w]; h];
+    for (wy, row) in dist.iter().enumerate() {
+        for (wx, d) in row.iter().enumerate() {
+            if d.is_some() {
+                for cells in reachable.iter_mut().skip(wy).take(n) {
+                    for cell in cells.iter_mut().skip(wx).take(n) { *cell = true; }
+                }
+            }
+        }
+    }
+
+    let objectives = pickups(maze)
+        .map(|(col, row, tile)| {
+            let steps = pickup_steps(&dist, n, col, row);
+            Objective { col, row, tile, steps, distance: steps.map(|s| s as f32 * bs) }
+        })
+        .collect();
+
+    let dead_zones = dead_zones(maze, &reachable, w, h);
+
+    ReachReport { reachable, objectives, dead_zones }
+}
+
+fn is_open(c: char, keys: &[KeyColor]) -> bool {
+    match KeyColor::from_lock_tile(c) {
+        Some(k) => keys.contains(&k),
+        None => c == ' ' || is_door(c),
+    }
+}
+
+/// BFS sobre ventanas de n×n celdas (indexadas por su esquina superior izquierda).
+/// Devuelve los pasos hasta cada ventana alcanzable.
+fn flood(
+    maze: &[Vec<char>],
+    w: usize,
+    h: usize,
+    n: usize,
+    start: (usize, usize),
+    keys: &[KeyColor],
+    collected: &[Vec<bool>],
+) -> Vec<Vec<Option<usize>>> {
+    let mut dist = vec![vec![None; w]; h];
+    let open = |cx: usize, cy: usize| collected[cy][cx] || is_open(maze[cy][cx], keys);
+    let fits = |wx: usize, wy: usize| {
+        wx + n <= w && wy + n <= h &&
+        (wy..wy + n).all(|cy| (wx..wx + n).all(|cx| open(cx, cy)))
+    };
+    if !fits(start.0, start.1) { return dist; }
+
+    let mut queue = VecDeque::from([start]);
+    dist[start.1][start.0] = Some(0);
+    while let Some((x, y)) = queue.pop_front() {
+        let d = dist[y][x].unwrap_or(0);
+        for (nx, ny) in neighbours(x, y, w, h) {
+            if dist[ny][nx].is_none() && fits(nx, ny) {
+                dist[ny][nx] = Some(d + 1);
+                queue.push_back((nx, ny));
+            }
+        }
+    }
+    dist
+}
+
+/// Pasos hasta una ventana pegada (ortogonalmente) a la celda del objeto
+fn pickup_steps(dist: &[Vec<Option<usize>>], n: usize, col: usize, row: usize) -> Option<usize> {
+    let mut best: Option<usize> = None;
+    for (wy, line) in dist.iter().enumerate() {
+        for (wx, d) in line.iter().enumerate() {
+            let Some(d) = *d else { continue };
+            let covers = |c: usize, lo: usize| c >= lo && c < lo + n;
+            // La celda del objeto toca la ventana por un lado
+            let touches = (covers(row, wy) && (col + 1 == wx || col == wx + n))
+                || (covers(col, wx) && (row + 1 == wy || row == wy + n));
+            if touches {
+                best = Some(best.map_or(d, |b| b.min(d)));
+            }
+        }
+    }
+    best
+}
+
+fn pickups(maze: &[Vec<char>]) -> impl Iterator<Item = (usize, usize, char)> + '_ {
+    maze.iter().enumerate().flat_map(|(row, line)| {
+        line.iter().enumerate()
+            .filter(|(_, c)| is_pickup(**c))
+            .map(move |(col, &c)| (col, row, c))
+    })
+}
+
+/// Componentes conexas de celdas libres que no se alcanzan
+fn dead_zones(maze: &[Vec<char>], reachable: &[Vec<bool>], w: usize, h: usize) -> Vec<Vec<(usize, usize)>> {
+    let mut seen = vec![vec![false; w]; h];
+    let mut zones = Vec::new();
+    for sy in 0..h {
+        for sx in 0..w {
+            if seen[sy][sx] || reachable[sy][sx] || maze[sy][sx] != ' ' { continue; }
+            let mut zone = Vec::new();
+            let mut queue = VecDeque::from([(sx, sy)]);
+            seen[sy][sx] = true;
+            while let Some((x, y)) = queue.pop_front() {
+                zone.push((x, y));
+                for (nx, ny) in neighbours(x, y, w, h) {
+                    if !seen[ny][nx] && !reachable[ny][nx] && maze[ny][nx] == ' ' {
+                        seen[ny][nx] = true;
+                        queue.push_back((nx, ny));
+                    }
+                }
+            }
+            zones.push(zone);
+        }
+    }
+    zones
+}
+
+/// Vecinos ortogonales dentro de la cuadrícula
+pub fn neighbours(x: usize, y: usize, w: usize, h: usize) -> impl Iterator<Item = (usize, usize)> {
+    [(0i32, -1i32), (1, 0), (0, 1), (-1, 0)].into_iter().filter_map(move |(dx, dy)| {
+        let (nx, ny) = (x as i32 + dx, y as i32 + dy);
+        (nx >= 0 && ny >= 0 && (nx as usize) < w && (ny as usize) < h).then_some((nx as usize, ny as usize))
+    })
+}
@@ -0,0 +1,123 @@
+use crate::bindings::Actions;
+use crate::config::TICK;
+use crate::game::LevelSource;
+use crate::map::MapError;
+use crate::platform::NullPlatform;
+use crate::settings::Gameplay;
+use crate::world::{Outcome, World};
+
+/// Partida grabada: el nivel (con su semilla si es aleatorio) y las acciones
+/// de cada paso de simulación. Como la simulación es a paso fijo, repetir las
+/// mismas entradas reproduce la partida exacta. Se guardan acciones y no
+/// teclas, así la repetición no depende de los controles de quien la grabó.
+///
+/// El archivo es texto: una cabecera con el nivel (y las velocidades, si no
+/// son las de siempre: `gameplay <avance> <correr> <giro>`) y luego una línea por
+/// racha de pasos iguales, `<pasos> <acciones activas> <acciones nuevas> <giro>`,
+/// con las acciones en hexadecimal y el giro del mouse en radianes.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Replay {
+    pub level: LevelSource,
+    /// Velocidades con las que se grabó
+    pub gameplay: Gameplay,
+    pub ticks: Vec<Actions>,
+}
+
+const HEADER: &str = "replay 2";
+
+impl Replay {
+    pub fn new(level: LevelSource) -> Self {
+        Replay { level, gameplay: Gameplay::default(), ticks: Vec::new() }
+    }
+
+    pub fn to_text(&self) -> String {
+        let mut out = format!("{}\n", HEADER);
+        match &self.level {
+            LevelSource::File(path) => out += &format!("level {}\n", path),
+            LevelSource::Random(seed) => out += &format!("seed {}\n", seed),
+        }
+        let g = self.gameplay;
+        if g != Gameplay::default() {
+            out += &format!("gameplay {} {} {}\n", g.move_speed, g.run_multiplier, g.rot_speed);
+        }
+        let mut i = 0;
+        while i < self.ticks.len() {
+            let actions = self.ticks[i];
+            let run = self.ticks[i..].iter().take_while(|&&t| t == actions).count();
+            out += &format!("{} {:x} {:x} {}\n", run, actions.down, actions.pressed, actions.turn);
+            i += run;
+        }
+        out
+    }
+
+    /// `path` solo se usa en los mensajes de error
+    pub fn parse(text: &str, path: &str) -> Result<Self, MapError> {
+        let error = |line: usize, message: &str| MapError::Parse {
+            path: path.to_string(),
+            message: format!("línea {}: {}", line + 1, message),
+        };
+        let mut lines = text.lines().enumerate().peekable();
+        if lines.next().map(|(_, l)| l.trim()) != Some(HEADER) {
+            return Err(error(0, &format!("falta la cabecera '{}'", HEADER)));
+        }
+        let level = match lines.next() {
+            Some((_, l)) if l.starts_with("level ") => LevelSource::File(l["level ".len()..].trim().to_string()),
+            Some((n, l)) if l.starts_with("seed ") => LevelSource::Random(
+                l["seed ".len()..].trim().parse().map_err(|_| error(n, "semilla inválida"))?,
+            ),
+            _ => return Err(error(1, "se esperaba 'level <ruta>' o 'seed <n>'")),
+        };
+
+        let mut gameplay = Gameplay::default();
+        if let Some((n, l)) = lines.next_if(|(_, l)| l.starts_with("gameplay ")) {
+            let values: Vec<f32> = l["gameplay ".len()..].split_whitespace()
+                .map(|v| v.parse().map_err(|_| error(n, "velocidad inválida")))
+                .collect::<Result<_, _>>()?;
+            let [move_speed, run_multiplier, rot_speed] = values[..] else {
+                return Err(error(n, "se esperaba 'gameplay <avance> <correr> <giro>'"));
+            };
+            gameplay = Gameplay { move_speed, run_multiplier, rot_speed };
+        }
+
+        let mut ticks = Vec::new();
+        for (n, line) in lines {
+            if line.trim().is_empty() { continue; }
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            let [run, down, pressed, turn] = fields[..] else {
+                return Err(error(n, "se esperaba '<pasos> <activas> <nuevas> <giro>'"));
+            };
+            let run: usize = run.parse().map_err(|_| error(n, "cantidad de pasos inválida"))?;
+            let actions = Actions {
+                down: u32::from_str_radix(down, 16).map_err(|_| error(n, "acciones inválidas"))?,
+                pressed: u32::from_str_radix(pressed, 16).map_err(|_| error(n, "acciones inválidas"))?,
+                turn: turn.parse().map_err(|_| error(n, "giro inválido"))?,
+            };
+            ticks.extend(std::iter::repeat_n(actions, run));
+        }
+        Ok(Replay { level, gameplay, ticks })
+    }
+
+    pub fn load(path: &str) -> Result<Self, MapError> {
+        let text = std::fs::read_to_string(path)
+            .map_err(|source| MapError::Io { path: path.to_string(), source })?;
+        Self::parse(&text, path)
+    }
+
+    pub fn save(&self, path: &str) -> std::io::Result<()> {
+        std::fs::write(path, self.to_text())
+    }
+
+    /// Reproduce la partida sin ventana ni audio hasta que se acaban las
+    /// entradas o la partida termina. Devuelve el mundo final y el resultado.
+    pub fn run(&self) -> Result<(World, Outcome), MapError> {
+        let mut world = World::new(self.level.load()?);
+        world.gameplay = self.gameplay;
+        let mut platform = NullPlatform::new();
+        let mut outcome = Outcome::Playing;
+        for actions in &self.ticks {
+            outcome = world.update(TICK, actions, &mut platform);
+            if outcome != Outcome::Playing { break; }
+        }
+        Ok((world, outcome))
+    }
+}
@@ -0,0 +1,192 @@
+use crate::audio::Channel;
+use crate::bindings::{Bindings, Controls};
+use crate::config::{MOVE_SPEED, ROT_SPEED, RUN_MULTIPLIER};
+use crate::map::MapError;
+use serde::{Deserialize, Serialize, Serializer};
+use std::path::{Path, PathBuf};
+
+/// Ajustes del jugador que se guardan entre partidas: ventana, campo de
+/// visión, volúmenes, velocidades y controles. Se leen de un TOML en la
+/// carpeta de configuración del usuario; lo que falta toma el valor por
+/// defecto. `BLOCK_SIZE` no está aquí: es la escala de los mapas, no una
+/// preferencia.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Settings {
+    pub video: Video,
+    pub audio: Volumes,
+    pub gameplay: Gameplay,
+    pub controls: Controls,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Video {
+    pub width: usize,
+    pub height: usize,
+    #[serde(serialize_with = "short_float")]
+    pub fov: f32, // grados
+    /// Cuántas veces se agranda la ventana respecto al framebuffer
+    pub scale: usize,
+    /// Ventana sin bordes, lo más grande que entre en la pantalla
+    pub fullscreen: bool,
+}
+
+impl Default for Video {
+    fn default() -> Self {
+        Video { width: 640, height: 480, fov: 90.0, scale: 1, fullscreen: false }
+    }
+}
+
+impl Video {
+    pub fn fov_radians(&self) -> f32 {
+        self.fov.to_radians()
+    }
+}
+
+/// Volúmenes de 0.0 a 1.0
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Volumes {
+    #[serde(serialize_with = "short_float")]
+    pub master: f32,
+    #[serde(serialize_with = "short_float")]
+    pub music: f32,
+    #[serde(serialize_with = "short_float")]
+    pub effects: f32,
+}
+
+impl Default for Volumes {
+    fn default() -> Self {
+        Volumes { master: 1.0, music: 1.0, effects: 1.0 }
+    }
+}
+
+impl Volumes {
+    pub fn get(&self, channel: Channel) -> f32 {
+        match channel {
+            Channel::Master => self.master,
+            Channel::Music => self.music,
+            Channel::Effects => self.effects,
+        }
+    }
+
+    pub fn get_mut(&mut self, channel: Channel) -> &mut f32 {
+        match channel {
+            Channel::Master => &mut self.master,
+            Channel::Music => &mut self.music,
+            Channel::Effects => &mut self.effects,
+        }
+    }
+}
+
+/// Velocidades del jugador (por segundo)
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Gameplay {
+    #[serde(serialize_with = "short_float")]
+    pub move_speed: f32, // unidades del mundo
+    #[serde(serialize_with = "short_float")]
+    pub run_multiplier: f32,
+    #[serde(serialize_with = "short_float")]
+    pub rot_speed: f32, // radianes
+}
+
+impl Default for Gameplay {
+    fn default() -> Self {
+        Gameplay { move_speed: MOVE_SPEED, run_multiplier: RUN_MULTIPLIER, rot_speed: ROT_SPEED }
+    }
+}
+
+/// Rangos aceptados; fuera de ellos se usa el valor por defecto
+pub const WIDTH_RANGE: (usize, usize) = (320, 3840);
+pub const HEIGHT_RANGE: (usize, usize) = (240, 2160);
+pub const FOV_RANGE: (f32, f32) = (30.0, 150.0);
+pub const SENSITIVITY_RANGE: (f32, f32) = (0.0005, 0.02);
+/// Escalas de ventana que acepta minifb
+pub const SCALES: [usize; 4] = [1, 2, 4, 8];
+
+impl Settings {
+    /// `<carpeta de configuración>/ej_wolfenstain/settings.toml`, si el
+    /// sistema tiene una
+    pub fn default_path() -> Option<PathBuf> {
+        dirs::config_dir().map(|dir| dir.join("ej_wolfenstain").join("settings.toml"))
+    }
+
+    pub fn load(path: &Path) -> Result<Self, MapError> {
+        let name = path.display().to_string();
+        let text = std::fs::read_to_string(path)
+            .map_err(|source| MapError::Io { path: name.clone(), source })?;
+        Self::parse(&text, &name)
+    }
+
+    /// `path` solo se usa en los mensajes de error
+    pub fn parse(text: &str, path: &str) -> Result<Self, MapError> {
+        toml::from_str(text).map_err(|e| MapError::Parse { path: path.to_string(), message: e.to_string() })
+    }
+
+    /// Escribe el archivo, creando la carpeta si hace falta
+    pub fn save(&self, path: &Path) -> std::io::Result<()> {
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir)?;
+        }
+        let text = toml::to_string_pretty(self).map_err(std::io::Error::other)?;
+        std::fs::write(path, text)
+    }
+
+    /// Corrige los valores fuera de rango y devuelve un aviso por cada uno
+    pub fn validate(&mut self) -> Vec<String> {
+        let mut warnings = Vec::new();
+        let defaults = Settings::default();
+        check(&mut warnings, "video.width", &mut self.video.width, WIDTH_RANGE, defaults.video.width);
+        check(&mut warnings, "video.height", &mut self.video.height, HEIGHT_RANGE, defaults.video.height);
+        check(&mut warnings, "video.fov", &mut self.video.fov, FOV_RANGE, defaults.video.fov);
+        if !SCALES.contains(&self.video.scale) {
+            warnings.push(format!("video.scale = {} no es 1, 2, 4 ni 8; se usa 1", self.video.scale));
+            self.video.scale = 1;
+        }
+        let volumes = [
+            ("audio.master", &mut self.audio.master),
+            ("audio.music", &mut self.audio.music),
+            ("audio.effects", &mut self.audio.effects),
+        ];
+        for (name, volume) in volumes {
+            check(&mut warnings, name, volume, (0.0, 1.0), 1.0);
+        }
+        let g = defaults.gameplay;
+        check(&mut warnings, "gameplay.move_speed", &mut self.gameplay.move_speed, (1.0, 2000.0), g.move_speed);
+        check(&mut warnings, "gameplay.run_multiplier", &mut self.gameplay.run_multiplier, (1.0, 5.0), g.run_multiplier);
+        check(&mut warnings, "gameplay.rot_speed", &mut self.gameplay.rot_speed, (0.1, 20.0), g.rot_speed);
+        check(&mut warnings, "controls.mouse_sensitivity", &mut self.controls.mouse_sensitivity,
+            SENSITIVITY_RANGE, defaults.controls.mouse_sensitivity);
+        if let Err(e) = Bindings::from_controls(&self.controls) {
+            warnings.push(format!("controls: {}; se usan los controles por defecto", e));
+            self.controls = defaults.controls;
+        }
+        warnings
+    }
+
+    /// Teclas y mouse listos para usar (los de siempre si el archivo tiene errores)
+    pub fn bindings(&self) -> Bindings {
+        Bindings::from_controls(&self.controls).unwrap_or_default()
+    }
+}
+
+fn check<T: PartialOrd + std::fmt::Display + Copy>(
+    warnings: &mut Vec<String>,
+    name: &str,
+    value: &mut T,
+    (min, max): (T, T),
+    default: T,
+) {
+    // `!(a <= b)` también atrapa NaN
+    if !(min <= *value && *value <= max) {
+        warnings.push(format!("{} = {} fuera de rango ({} a {}); se usa {}", name, value, min, max, default));
+        *value = default;
+    }
+}
+
+/// Guarda 0.7 y no 0.699999988: toml escribe los f32 pasándolos a f64
+pub(crate) fn short_float<S: Serializer>(value: &f32, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.serialize_f64(value.to_string().parse().unwrap_or(*value as f64))
+}
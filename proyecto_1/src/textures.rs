@@ -5,21 +5,46 @@ pub struct Textures {
     pub map: HashMap<char, DynamicImage>,
 }
 
+impl Default for Textures {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl Textures {
     pub fn new() -> Self {
         let mut map = HashMap::new();
 
-        map.insert('#', image::open("textures/pared.png").unwrap());
-        map.insert('A', image::open("textures/A.png").unwrap());
-        map.insert('B', image::open("textures/B.png").unwrap());
-        map.insert('C', image::open("textures/C.png").unwrap());
-        map.insert('1', image::open("textures/ok.png").unwrap());
-        map.insert('2', image::open("textures/enemy1.png").unwrap());
-        map.insert('3', image::open("textures/enemy2.png").unwrap());
+        load_into(&mut map, '#', "textures/pared.png");
+        load_into(&mut map, 'A', "textures/A.png");
+        load_into(&mut map, 'B', "textures/B.png");
+        load_into(&mut map, 'C', "textures/C.png");
+        load_into(&mut map, 'D', "textures/puerta.png");
+        load_into(&mut map, 'd', "textures/puerta.png");
+        load_into(&mut map, 'R', "textures/puerta_roja.png");
+        load_into(&mut map, 'G', "textures/puerta_verde.png");
+        load_into(&mut map, 'Y', "textures/puerta_amarilla.png");
+        load_into(&mut map, 'r', "textures/llave_roja.png");
+        load_into(&mut map, 'g', "textures/llave_verde.png");
+        load_into(&mut map, 'y', "textures/llave_amarilla.png");
+        load_into(&mut map, 'a', "textures/armadura.png");
+        load_into(&mut map, 'm', "textures/municion.png");
+        load_into(&mut map, '1', "textures/ok.png");
+        load_into(&mut map, '2', "textures/enemy1.png");
+        load_into(&mut map, '3', "textures/enemy2.png");
+        load_into(&mut map, 'e', "sprites/enemy1.png");
+        load_into(&mut map, 'E', "sprites/enemy2.png");
 
         Textures { map }
     }
 
+    /// Aplica la leyenda de un nivel: agrega o reemplaza texturas por carácter
+    pub fn apply_legend(&mut self, legend: &HashMap<char, String>) {
+        for (&c, path) in legend {
+            load_into(&mut self.map, c, path);
+        }
+    }
+
     /// Obtener color de la textura en coordenada u,v (0..1) para paredes
     pub fn sample(&self, c: char, u: f32, v: f32) -> u32 {
         if let Some(img) = self.map.get(&c) {
@@ -57,4 +82,12 @@ impl Textures {
             0xFFFFFF
         }
     }
-}
\ No newline at end of file
+}
+
+/// Carga una textura si existe; las que faltan se muestran en blanco al muestrear
+fn load_into(map: &mut HashMap<char, DynamicImage>, c: char, path: &str) {
+    match image::open(path) {
+        Ok(img) => { map.insert(c, img); }
+        Err(e) => eprintln!("No se pudo cargar la textura {}: {}", path, e),
+    }
+}
@@ -0,0 +1,80 @@
+use crate::door::Doors;
+use crate::enemy::Enemy;
+use crate::player::Player;
+use crate::raycaster::cast_ray_from;
+
+/// Arma de impacto instantáneo: el disparo es un rayo a lo largo de `player.a`
+pub struct Weapon {
+    pub damage: i32,
+    pub range: f32,    // unidades del mundo
+    pub cooldown: f32, // segundos entre disparos
+    timer: f32,
+}
+
+/// Resultado de apretar el gatillo
+pub enum Shot {
+    /// Sin munición o todavía recargando
+    Blocked,
+    /// Disparó pero no dio a nadie
+    Miss,
+    /// Dio a un enemigo; `killed` si lo dejó sin vida
+    Hit { killed: bool },
+}
+
+impl Weapon {
+    pub fn pistol() -> Self {
+        Weapon { damage: 25, range: 1024.0, cooldown: 0.35, timer: 0.0 }
+    }
+
+    pub fn update(&mut self, dt: f32) {
+        self.timer = (self.timer - dt).max(0.0);
+    }
+
+    pub fn ready(&self) -> bool {
+        self.timer <= 0.0
+    }
+
+    /// Dispara si puede: gasta una bala y daña al enemigo más cercano que
+    /// cruce el rayo antes de la primera pared
+    pub fn fire(
+        &mut self,
+        player: &mut Player,
+        enemies: &mut [Enemy],
+        maze: &[Vec<char>],
+        doors: &Doors,
+        block_size: usize,
+    ) -> Shot {
+        if !self.ready() || player.ammo == 0 {
+            return Shot::Blocked;
+        }
+        self.timer = self.cooldown;
+        player.ammo -= 1;
+
+        let wall = cast_ray_from(maze, doors, player.pos.x, player.pos.y, player.a, block_size);
+        let reach = wall.distance.min(self.range);
+        let (dir_x, dir_y) = (player.a.cos(), player.a.sin());
+
+        // Intersección del rayo con el círculo de cada enemigo
+        let mut best: Option<(usize, f32)> = None;
+        for (index, enemy) in enemies.iter().enumerate() {
+            if !enemy.is_alive() { continue; }
+            let dx = enemy.pos.x - player.pos.x;
+            let dy = enemy.pos.y - player.pos.y;
+            let along = dx * dir_x + dy * dir_y;
+            let across = (dx * dir_y - dy * dir_x).abs();
+            // La figura del sprite es más ancha que su caja de colisión
+            if along <= 0.0 || along > reach || across > enemy.radius * 2.0 { continue; }
+            if best.is_none_or(|(_, d)| along < d) {
+                best = Some((index, along));
+            }
+        }
+
+        match best {
+            Some((index, _)) => {
+                enemies[index].take_damage(self.damage);
+                Shot::Hit { killed: !enemies[index].is_alive() }
+            }
+            None => Shot::Miss,
+        }
+    }
+}
@@ -0,0 +1,271 @@
+use crate::audio::{SoundId, Spatial};
+use crate::bindings::{Action, Actions};
+use crate::door::{is_door, Doors};
+use crate::enemy::{Enemy, EnemyState};
+use crate::inventory::{is_collectible, KeyColor, AMMO_TILE, ARMOUR_TILE};
+use crate::framebuffer::Framebuffer;
+use crate::hud::{draw_text, text_width, HudInfo, StatusBar, WeaponView};
+use crate::level::Level;
+use crate::platform::Platform;
+use crate::player::Player;
+use crate::raycaster::{self, render3d, render_minimap, Scene, Sprite};
+use crate::textures::Textures;
+use crate::weapon::{Shot, Weapon};
+use crate::config::BLOCK_SIZE;
+use crate::settings::Gameplay;
+use rusttype::Font;
+
+const USE_DISTANCE: f32 = 96.0; // Alcance para abrir puertas
+const PICKUP_DISTANCE: f32 = 50.0;
+
+/// Cómo va la partida después de un paso
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Outcome {
+    Playing,
+    Won,
+    Lost,
+}
+
+/// Estado de una partida en curso y sus reglas: movimiento, puertas,
+/// enemigos, disparos y recolección. No sabe nada de ventanas ni de audio,
+/// todo pasa por `Platform`.
+pub struct World {
+    pub level: Level,
+    pub maze: Vec<Vec<char>>, // copia del nivel; los objetos recogidos se borran
+    pub doors: Doors,
+    pub player: Player,
+    pub enemies: Vec<Enemy>,
+    pub weapon: Weapon,
+    pub show_map: bool,
+    /// Velocidades del jugador, de los ajustes
+    pub gameplay: Gameplay,
+    weapon_view: WeaponView,
+    status_bar: StatusBar,
+    textures: Textures,
+}
+
+impl World {
+    pub fn new(level: Level) -> Self {
+        let maze = level.maze.clone();
+        let doors = Doors::from_maze(&maze);
+        let enemies = level.enemies.iter()
+            .map(|&(i, j, kind)| Enemy::new(i, j, kind, &maze, &doors, BLOCK_SIZE))
+            .collect();
+        let player = level.spawn_player(
+            BLOCK_SIZE,
+            std::f32::consts::FRAC_PI_2, // FOV ~ 90°
+        );
+        let mut textures = Textures::new();
+        textures.apply_legend(&level.textures);
+
+        World {
+            level,
+            maze,
+            doors,
+            player,
+            enemies,
+            weapon: Weapon::pistol(),
+            show_map: true,
+            gameplay: Gameplay::default(),
+            weapon_view: WeaponView::new(),
+            status_bar: StatusBar::new(),
+            textures,
+        }
+    }
+
+    /// Avanza la partida `dt` segundos con las acciones del jugador.
+    /// `platform` solo se usa para los sonidos.
+    pub fn update(&mut self, dt: f32, actions: &Actions, platform: &mut dyn Platform) -> Outcome {
+        // Animación de puertas
+        for (i, j) in self.doors.update(dt, &self.player, BLOCK_SIZE) {
+            let spatial = self.spatial_at_cell(i, j);
+            platform.play_sound_at(SoundId::Door, spatial);
+        }
+
+        // Enemigos: patrullan, persiguen y atacan
+        let hunting = |e: &Enemy| matches!(e.state, EnemyState::Chase | EnemyState::Attack);
+        for n in 0..self.enemies.len() {
+            let was_hunting = hunting(&self.enemies[n]);
+            let attacked = self.enemies[n].update(dt, &self.player, &self.maze, &self.doors, BLOCK_SIZE);
+            let enemy = &self.enemies[n];
+            let alert = !was_hunting && hunting(enemy);
+            if !alert && !attacked { continue; }
+            // El rayo de oclusión solo hace falta si va a sonar algo
+            let spatial = self.spatial_at(enemy.pos.x, enemy.pos.y);
+            if alert {
+                platform.play_sound_at(SoundId::EnemyAlert, spatial);
+            }
+            if attacked {
+                self.player.take_damage(enemy.damage());
+                platform.play_sound_at(SoundId::EnemyAttack, spatial);
+            }
+        }
+
+        // Disparo
+        self.weapon.update(dt);
+        if actions.is_pressed(Action::Fire) {
+            match self.weapon.fire(&mut self.player, &mut self.enemies, &self.maze, &self.doors, BLOCK_SIZE) {
+                Shot::Blocked => {}
+                Shot::Miss | Shot::Hit { .. } => {
+                    self.weapon_view.fire();
+                    platform.play_sound(SoundId::Shot);
+                }
+            }
+        }
+        self.enemies.retain(Enemy::is_alive);
+
+        // Giro con teclas y con el mouse
+        let Gameplay { move_speed, run_multiplier, rot_speed } = self.gameplay;
+        if actions.is_down(Action::TurnLeft) {
+            self.player.rotate_left(rot_speed * dt);
+        }
+        if actions.is_down(Action::TurnRight) {
+            self.player.rotate_right(rot_speed * dt);
+        }
+        self.player.rotate_right(actions.turn);
+
+        // Avance y paso lateral; en diagonal no se va más rápido
+        let axis = |pos, neg| actions.is_down(pos) as i32 - actions.is_down(neg) as i32;
+        let forward = axis(Action::MoveForward, Action::MoveBackward) as f32;
+        let side = axis(Action::StrafeRight, Action::StrafeLeft) as f32;
+        let moving = forward != 0.0 || side != 0.0;
+        if moving {
+            let mut step = move_speed * dt / (forward * forward + side * side).sqrt();
+            if actions.is_down(Action::Run) {
+                step *= run_multiplier;
+            }
+            self.player.move_forward(forward * step, &self.maze, &self.doors, BLOCK_SIZE);
+            self.player.strafe(side * step, &self.maze, &self.doors, BLOCK_SIZE);
+        }
+        self.weapon_view.update(dt, moving);
+        self.status_bar.update(dt);
+
+        // Usar: recoge el objeto de enfrente o, si no hay, abre la puerta
+        if actions.is_pressed(Action::Use) && !self.pick_up(platform) {
+            self.use_door(platform);
+        }
+        if actions.is_pressed(Action::Map) {
+            self.show_map = !self.show_map;
+        }
+
+        // El objetivo más cercano zumba para guiar al jugador
+        platform.set_loop(SoundId::Hum, self.nearest_collectible().map(|(i, j)| self.spatial_at_cell(i, j)));
+
+        if self.player.is_dead() {
+            Outcome::Lost
+        } else if self.player.has_all_items(self.level.total_items) {
+            Outcome::Won
+        } else {
+            Outcome::Playing
+        }
+    }
+
+    /// Recoge el objeto que el jugador tiene enfrente, si está cerca
+    fn pick_up(&mut self, platform: &mut dyn Platform) -> bool {
+        // Lanzar un rayo hacia adelante para detectar objetos
+        let hit = raycaster::cast_ray(&self.maze, &self.doors, &self.player, self.player.a, BLOCK_SIZE);
+        let Some(obj_type) = hit.object_type else { return false };
+        if hit.distance >= PICKUP_DISTANCE { return false; }
+
+        self.player.pick_up(obj_type);
+        let message = match obj_type {
+            ARMOUR_TILE => format!("Armadura {}%", self.player.armour),
+            AMMO_TILE => format!("Munición {}", self.player.ammo),
+            _ => match KeyColor::from_key_tile(obj_type) {
+                Some(color) => format!("Llave {}", color.name()),
+                None => format!("Objeto {}/{}", self.player.get_collected_items(), self.level.total_items),
+            },
+        };
+        self.status_bar.show(message);
+        // "Eliminar" el objeto del mapa (reemplazar con espacio)
+        self.maze[hit.cell_y][hit.cell_x] = ' ';
+        platform.play_sound(SoundId::Pickup);
+        true
+    }
+
+    /// Abre o cierra la puerta que el jugador tiene enfrente
+    fn use_door(&mut self, platform: &mut dyn Platform) {
+        let hit = raycaster::cast_ray(&self.maze, &self.doors, &self.player, self.player.a, BLOCK_SIZE);
+        if !is_door(hit.impact) || hit.distance >= USE_DISTANCE { return; }
+        let before = self.doors.state(hit.cell_x, hit.cell_y);
+        if self.doors.activate(hit.cell_x, hit.cell_y, &self.player.inventory) {
+            if before != self.doors.state(hit.cell_x, hit.cell_y) {
+                platform.play_sound_at(SoundId::Door, self.spatial_at_cell(hit.cell_x, hit.cell_y));
+            }
+        } else if let Some(color) = self.doors.required_key(hit.cell_x, hit.cell_y) {
+            self.status_bar.show(format!("Necesitas la llave {}", color.name()));
+        }
+    }
+
+    /// Cómo oye el jugador un sonido en (x, y): se apaga con la distancia y
+    /// si hay una pared en medio
+    fn spatial_at(&self, x: f32, y: f32) -> Spatial {
+        let p = &self.player;
+        let (dx, dy) = (x - p.pos.x, y - p.pos.y);
+        let hit = raycaster::cast_ray_from(&self.maze, &self.doors, p.pos.x, p.pos.y, dy.atan2(dx), BLOCK_SIZE);
+        // Margen de una celda: los objetos y las puertas frenan el rayo en su propia celda
+        let margin = BLOCK_SIZE as f32;
+        let occluded = hit.distance + margin < dx.hypot(dy);
+        Spatial::at(p.pos.x, p.pos.y, p.a, x, y, occluded)
+    }
+
+    fn spatial_at_cell(&self, i: usize, j: usize) -> Spatial {
+        let bs = BLOCK_SIZE as f32;
+        self.spatial_at((i as f32 + 0.5) * bs, (j as f32 + 0.5) * bs)
+    }
+
+    /// Celda del objetivo más cercano (en línea recta) que queda en el mapa
+    fn nearest_collectible(&self) -> Option<(usize, usize)> {
+        let bs = BLOCK_SIZE as f32;
+        let (px, py) = (self.player.pos.x / bs, self.player.pos.y / bs);
+        let distance = |&(i, j): &(usize, usize)| (i as f32 + 0.5 - px).hypot(j as f32 + 0.5 - py);
+        self.maze.iter().enumerate()
+            .flat_map(|(j, row)| row.iter().enumerate().filter(|(_, c)| is_collectible(**c)).map(move |(i, _)| (i, j)))
+            .min_by(|a, b| distance(a).total_cmp(&distance(b)))
+    }
+
+    /// Aviso que se está mostrando arriba de la vista
+    pub fn message(&self) -> Option<&str> {
+        self.status_bar.message()
+    }
+
+    pub fn show_message(&mut self, text: impl Into<String>) {
+        self.status_bar.show(text);
+    }
+
+    /// Corta los sonidos en bucle de la partida (al pausar o salir)
+    pub fn stop_loops(platform: &mut dyn Platform) {
+        platform.set_loop(SoundId::Hum, None);
+    }
+
+    /// Dibuja la vista 3D, el arma y la barra de estado
+    pub fn render(&self, framebuffer: &mut Framebuffer, font: &Font<'_>, fps: f32) {
+        framebuffer.clear(0x000000);
+        let sprites: Vec<Sprite> = self.enemies.iter().map(Enemy::sprite).collect();
+        let scene = Scene {
+            maze: &self.maze,
+            doors: &self.doors,
+            surfaces: &self.level.surfaces,
+            textures: &self.textures,
+            sprites: &sprites,
+        };
+        render3d(framebuffer, &self.player, &scene, BLOCK_SIZE);
+        if self.show_map {
+            render_minimap(framebuffer, &self.player, &self.maze, 10, 10, 4, BLOCK_SIZE);
+        }
+
+        // Arma en primera persona, apoyada sobre la barra de estado
+        let bar_top = framebuffer.height - StatusBar::height(framebuffer.height);
+        self.weapon_view.draw(framebuffer, bar_top);
+
+        // Barra de estado inferior
+        let info = HudInfo { level_name: &self.level.name, total_items: self.level.total_items };
+        self.status_bar.draw(framebuffer, font, &self.player, &info);
+
+        // FPS (esquina superior derecha)
+        let fps_text = format!("FPS: {:.0}", fps);
+        let fps_size = framebuffer.height as f32 / 27.0;
+        let fps_x = framebuffer.width.saturating_sub(text_width(font, &fps_text, fps_size) + 14);
+        draw_text(framebuffer, font, &fps_text, fps_x, 14, 0xFFFF00, fps_size);
+    }
+}
@@ -0,0 +1,51 @@
+//! Carga de efectos y backend de audio nulo.
+
+use ej_wolfenstain::audio::{AudioManager, Channel, Clip, SoundId, Spatial, HEARING_DISTANCE};
+use ej_wolfenstain::game::{TITLE_MUSIC, VICTORY_MUSIC};
+
+#[test]
+fn every_sound_effect_decodes() {
+    for id in SoundId::ALL {
+        let clip = Clip::load(id.path()).unwrap_or_else(|| panic!("{:?}", id));
+        assert!(clip.duration() > 0.0, "{:?}", id);
+    }
+    for path in [TITLE_MUSIC, VICTORY_MUSIC, "assets/musica_tension.wav"] {
+        assert!(Clip::load(path).is_some_and(|clip| clip.duration() >= 4.0), "{}", path);
+    }
+}
+
+#[test]
+fn null_backend_is_silent_but_keeps_settings() {
+    let mut audio = AudioManager::null();
+    assert!(audio.is_null());
+    for _ in 0..20 {
+        audio.play(SoundId::Shot);
+    }
+    let tracks = ["assets/music.ogg".to_string(), "assets/musica_tension.wav".to_string()];
+    audio.play_music(&tracks);
+    assert_eq!(audio.playlist(), tracks);
+    audio.set_volume(Channel::Music, 1.5);
+    audio.set_volume(Channel::Effects, 0.25);
+    audio.duck_music(0.3);
+    audio.stop_music();
+    assert_eq!(audio.volume(Channel::Music), 1.0);
+    assert_eq!(audio.volume(Channel::Effects), 0.25);
+    assert_eq!(audio.volume(Channel::Master), 1.0);
+}
+
+#[test]
+fn spatial_volume_and_pan() {
+    // Escucha en el origen mirando al este (+x); +y queda a la derecha
+    let near = Spatial::at(0.0, 0.0, 0.0, 100.0, 0.0, false);
+    let far = Spatial::at(0.0, 0.0, 0.0, 600.0, 0.0, false);
+    assert!(near.volume > far.volume && far.volume > 0.0);
+    assert!(near.pan.abs() < 1e-6);
+    assert_eq!(Spatial::at(0.0, 0.0, 0.0, HEARING_DISTANCE, 0.0, false).volume, 0.0);
+
+    let right = Spatial::at(0.0, 0.0, 0.0, 0.0, 200.0, false);
+    let left = Spatial::at(0.0, 0.0, 0.0, 0.0, -200.0, false);
+    assert!(right.pan > 0.99 && left.pan < -0.99);
+
+    let behind_wall = Spatial::at(0.0, 0.0, 0.0, 100.0, 0.0, true);
+    assert!(behind_wall.volume < near.volume * 0.5);
+}
@@ -0,0 +1,42 @@
+//! Sección de controles y traducción de teclas a acciones.
+
+use ej_wolfenstain::bindings::{Action, Bindings};
+use ej_wolfenstain::platform::{Input, Key};
+
+#[test]
+fn controls_round_trip() {
+    let defaults = Bindings::default();
+    assert!(!defaults.mouse_look);
+    let controls = defaults.to_controls();
+    assert_eq!(controls.keys["run"], ["Shift"]);
+    let back = Bindings::from_controls(&controls).unwrap();
+    for action in Action::ALL {
+        assert_eq!(back.keys(action), defaults.keys(action), "{:?}", action);
+    }
+}
+
+#[test]
+fn rebinding_keys_and_mouse() {
+    let text = r#"
+        mouse_look = true
+        mouse_sensitivity = 0.01
+        [keys]
+        fire = ["Ctrl", "F"]
+        turn_left = ["Q"]
+    "#;
+    let bindings = Bindings::parse(text, "prueba").unwrap();
+    assert_eq!(bindings.keys(Action::Fire), [Key::LeftCtrl, Key::F]);
+    assert_eq!(bindings.keys(Action::Use), [Key::E]); // sin cambios
+
+    let mut input = Input::default();
+    input.press(Key::F);
+    input.hold(Key::Q);
+    input.hold(Key::Space);
+    let actions = bindings.resolve(&input, -10.0);
+    assert!(actions.is_pressed(Action::Fire));
+    assert!(actions.is_down(Action::TurnLeft) && !actions.is_pressed(Action::TurnLeft));
+    assert!((actions.turn + 0.1).abs() < 1e-6);
+
+    assert!(Bindings::parse("[keys]\njump = [\"Space\"]", "prueba").is_err());
+    assert!(Bindings::parse("[keys]\nfire = [\"Z\"]", "prueba").is_err());
+}
@@ -0,0 +1,192 @@
+//! Flujo de pantallas de `Game` con `NullPlatform`.
+
+use ej_wolfenstain::framebuffer::Framebuffer;
+use ej_wolfenstain::audio::Channel;
+use ej_wolfenstain::game::{Game, Scene, TITLE_MUSIC, VICTORY_MUSIC};
+use ej_wolfenstain::hud::load_font;
+use ej_wolfenstain::platform::{Key, NullPlatform, Platform};
+
+const W: usize = 160;
+const H: usize = 120;
+
+/// Aprieta `key` durante un cuadro y devuelve si el juego sigue
+fn tap(game: &mut Game, platform: &mut NullPlatform, fb: &mut Framebuffer, key: Key) -> bool {
+    platform.press(key);
+    let running = game.frame(1.0 / 60.0, platform, fb);
+    platform.present(fb);
+    running
+}
+
+#[test]
+fn menu_to_play_pause_and_back() {
+    let mut game = Game::new(load_font("fonts/Arial.ttf"), W, H);
+    let mut platform = NullPlatform::new();
+    let mut fb = Framebuffer::new(W, H);
+
+    assert!(matches!(game.scene(), Scene::Title));
+    tap(&mut game, &mut platform, &mut fb, Key::Enter);
+    assert!(matches!(game.scene(), Scene::LevelSelect));
+    tap(&mut game, &mut platform, &mut fb, Key::Key1);
+    assert!(matches!(game.scene(), Scene::Playing(_)));
+    assert_eq!(platform.title, "Nivel 1");
+    assert_eq!(platform.music, ["./levels/../assets/music.ogg"]);
+
+    tap(&mut game, &mut platform, &mut fb, Key::Escape);
+    assert!(matches!(game.scene(), Scene::Paused { selected: 0 }));
+    assert!(game.world().is_some());
+    assert!(platform.music_duck < 1.0);
+    tap(&mut game, &mut platform, &mut fb, Key::Escape);
+    assert!(matches!(game.scene(), Scene::Playing(_)));
+    assert_eq!(platform.music_duck, 1.0);
+}
+
+#[test]
+fn pause_menu_navigation() {
+    let mut game = Game::new(load_font("fonts/Arial.ttf"), W, H);
+    let mut platform = NullPlatform::new();
+    let mut fb = Framebuffer::new(W, H);
+    tap(&mut game, &mut platform, &mut fb, Key::Enter);
+    tap(&mut game, &mut platform, &mut fb, Key::Key1);
+
+    // Reiniciar nivel deja al jugador en el punto de partida
+    let spawn = game.world().map(|w| (w.player.pos.x, w.player.pos.y));
+    platform.hold(Key::W);
+    for _ in 0..10 {
+        game.frame(1.0 / 60.0, &mut platform, &mut fb);
+    }
+    platform.release(Key::W);
+    tap(&mut game, &mut platform, &mut fb, Key::Escape);
+    // El tiempo de juego se detiene durante la pausa
+    let paused_at = game.world().map(|w| (w.player.pos.x, w.player.pos.y));
+    platform.hold(Key::W);
+    game.frame(1.0 / 60.0, &mut platform, &mut fb);
+    platform.release(Key::W);
+    assert_eq!(game.world().map(|w| (w.player.pos.x, w.player.pos.y)), paused_at);
+
+    tap(&mut game, &mut platform, &mut fb, Key::Down);
+    assert!(matches!(game.scene(), Scene::Paused { selected: 1 }));
+    tap(&mut game, &mut platform, &mut fb, Key::Enter);
+    assert!(matches!(game.scene(), Scene::Playing(_)));
+    assert_eq!(game.world().map(|w| (w.player.pos.x, w.player.pos.y)), spawn);
+
+    // Ajustes vuelve a la pausa; "Salir al menú" lleva a la selección de nivel
+    tap(&mut game, &mut platform, &mut fb, Key::Escape);
+    tap(&mut game, &mut platform, &mut fb, Key::Up);
+    tap(&mut game, &mut platform, &mut fb, Key::Up);
+    tap(&mut game, &mut platform, &mut fb, Key::Enter);
+    assert!(matches!(game.scene(), Scene::Settings { .. }));
+    tap(&mut game, &mut platform, &mut fb, Key::Escape);
+    assert!(matches!(game.scene(), Scene::Paused { selected: 2 }));
+    tap(&mut game, &mut platform, &mut fb, Key::Down);
+    tap(&mut game, &mut platform, &mut fb, Key::Enter);
+    assert!(matches!(game.scene(), Scene::LevelSelect));
+    assert!(game.world().is_none());
+    assert_eq!(platform.music_duck, 1.0);
+}
+
+#[test]
+fn back_from_level_select_and_quit_from_title() {
+    let mut game = Game::new(load_font("fonts/Arial.ttf"), W, H);
+    let mut platform = NullPlatform::new();
+    let mut fb = Framebuffer::new(W, H);
+
+    tap(&mut game, &mut platform, &mut fb, Key::Enter);
+    tap(&mut game, &mut platform, &mut fb, Key::Key5);
+    assert!(matches!(game.scene(), Scene::Settings { .. }));
+    tap(&mut game, &mut platform, &mut fb, Key::Escape);
+    assert!(matches!(game.scene(), Scene::LevelSelect));
+    tap(&mut game, &mut platform, &mut fb, Key::Escape);
+    assert!(matches!(game.scene(), Scene::Title));
+    assert!(!tap(&mut game, &mut platform, &mut fb, Key::Escape));
+}
+
+#[test]
+fn restart_after_game_over() {
+    let mut game = Game::new(load_font("fonts/Arial.ttf"), W, H);
+    let mut platform = NullPlatform::new();
+    let mut fb = Framebuffer::new(W, H);
+    tap(&mut game, &mut platform, &mut fb, Key::Enter);
+    tap(&mut game, &mut platform, &mut fb, Key::Key1);
+
+    game.world_mut().unwrap().player.take_damage(1000);
+    game.frame(1.0 / 60.0, &mut platform, &mut fb);
+    assert!(matches!(game.scene(), Scene::GameOver));
+
+    tap(&mut game, &mut platform, &mut fb, Key::R);
+    assert!(matches!(game.scene(), Scene::Playing(_)));
+    assert_eq!(game.world().map(|w| w.player.health), Some(100));
+}
+
+#[test]
+fn movement_does_not_depend_on_frame_rate() {
+    // Un segundo con W y A apretadas a 30, 60 y 120 cuadros por segundo
+    let run = |fps: u32| {
+        let mut game = Game::new(load_font("fonts/Arial.ttf"), W, H);
+        let mut platform = NullPlatform::new();
+        let mut fb = Framebuffer::new(W, H);
+        tap(&mut game, &mut platform, &mut fb, Key::Enter);
+        tap(&mut game, &mut platform, &mut fb, Key::Key1);
+        let start = game.world().map(|w| (w.player.pos.x, w.player.pos.y, w.player.a)).unwrap();
+        platform.hold(Key::W);
+        platform.hold(Key::A);
+        for _ in 0..fps {
+            game.frame(1.0 / fps as f32, &mut platform, &mut fb);
+            platform.present(&fb);
+        }
+        let end = game.world().map(|w| (w.player.pos.x, w.player.pos.y, w.player.a)).unwrap();
+        (start, end)
+    };
+    let (start, end) = run(60);
+    assert_ne!(start, end);
+    assert_eq!(run(30).1, end);
+    assert_eq!(run(120).1, end);
+}
+
+#[test]
+fn settings_volume_sliders() {
+    let mut game = Game::new(load_font("fonts/Arial.ttf"), W, H);
+    let mut platform = NullPlatform::new();
+    let mut fb = Framebuffer::new(W, H);
+    tap(&mut game, &mut platform, &mut fb, Key::Enter);
+    tap(&mut game, &mut platform, &mut fb, Key::Key5);
+
+    // Música: dos pasos abajo del general
+    tap(&mut game, &mut platform, &mut fb, Key::Down);
+    for _ in 0..3 {
+        tap(&mut game, &mut platform, &mut fb, Key::Left);
+    }
+    assert_eq!(platform.volume(Channel::Music), 0.7);
+    tap(&mut game, &mut platform, &mut fb, Key::Down);
+    for _ in 0..20 {
+        tap(&mut game, &mut platform, &mut fb, Key::Left);
+    }
+    assert_eq!(platform.volume(Channel::Effects), 0.0);
+    assert_eq!(platform.volume(Channel::Master), 1.0);
+}
+
+#[test]
+fn music_follows_the_scene() {
+    let mut game = Game::new(load_font("fonts/Arial.ttf"), W, H);
+    let mut platform = NullPlatform::new();
+    let mut fb = Framebuffer::new(W, H);
+    tap(&mut game, &mut platform, &mut fb, Key::Enter);
+    assert_eq!(platform.music, [TITLE_MUSIC]);
+
+    // El nivel 3 trae una lista de temas
+    tap(&mut game, &mut platform, &mut fb, Key::Key3);
+    assert_eq!(platform.music.len(), 2);
+    tap(&mut game, &mut platform, &mut fb, Key::Escape);
+    assert_eq!(platform.music.len(), 2); // la pausa solo la baja
+
+    tap(&mut game, &mut platform, &mut fb, Key::Escape);
+    let world = game.world_mut().unwrap();
+    let total = world.level.total_items;
+    for _ in 0..total {
+        world.player.pick_up('1');
+    }
+    tap(&mut game, &mut platform, &mut fb, Key::Space);
+    assert!(matches!(game.scene(), Scene::Victory));
+    assert_eq!(platform.music, [VICTORY_MUSIC]);
+    tap(&mut game, &mut platform, &mut fb, Key::Escape);
+    assert_eq!(platform.music, [TITLE_MUSIC]);
+}
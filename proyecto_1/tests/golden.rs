@@ -0,0 +1,103 @@
+//! Pruebas de imagen de referencia para el render.
+//!
+//! Cada caso dibuja una pose fija con `headless::render_level` y la compara con
+//! `tests/golden/<nombre>.png`. Si falla se escriben la imagen obtenida y un mapa
+//! de diferencias en `target/golden/`. Para regenerar las referencias después de
+//! un cambio visual intencional: `UPDATE_GOLDEN=1 cargo test golden`.
+
+use ej_wolfenstain::headless::{render_level, Camera};
+use ej_wolfenstain::level::Level;
+use ej_wolfenstain::BLOCK_SIZE;
+use image::{Rgb, RgbImage};
+use std::path::Path;
+
+/// Diferencia máxima por canal para considerar iguales dos píxeles
+const TOLERANCE: u8 = 4;
+/// Fracción de píxeles que puede superar la tolerancia (bordes con redondeo distinto)
+const MAX_BAD_FRACTION: f32 = 0.002;
+
+const WIDTH: usize = 160;
+const HEIGHT: usize = 120;
+
+/// Dibuja la pose (en celdas y grados) y la compara con su referencia
+fn check(name: &str, map: &str, x: f32, y: f32, degrees: f32) {
+    let level = Level::load(map).unwrap_or_else(|e| panic!("{}: {}", map, e));
+    let bs = BLOCK_SIZE as f32;
+    let camera = Camera { x: x * bs, y: y * bs, a: degrees.to_radians(), width: WIDTH, height: HEIGHT };
+    let actual = render_level(&level, &camera, BLOCK_SIZE).to_image();
+
+    let golden = format!("tests/golden/{}.png", name);
+    if std::env::var_os("UPDATE_GOLDEN").is_some() {
+        std::fs::create_dir_all("tests/golden").unwrap();
+        actual.save(&golden).unwrap();
+        return;
+    }
+    let expected = match image::open(&golden) {
+        Ok(img) => img.to_rgb8(),
+        Err(e) => panic!("falta la referencia {} ({}); generarla con UPDATE_GOLDEN=1", golden, e),
+    };
+    assert_eq!(expected.dimensions(), actual.dimensions(), "{}: tamaño distinto", name);
+
+    let (diff, bad) = compare(&expected, &actual);
+    let allowed = (WIDTH * HEIGHT) as f32 * MAX_BAD_FRACTION;
+    if bad as f32 > allowed {
+        let out = Path::new("target/golden");
+        std::fs::create_dir_all(out).unwrap();
+        actual.save(out.join(format!("{}.actual.png", name))).unwrap();
+        diff.save(out.join(format!("{}.diff.png", name))).unwrap();
+        panic!(
+            "{}: {} píxeles fuera de tolerancia (máximo {:.0}); ver target/golden/{}.diff.png",
+            name, bad, allowed, name
+        );
+    }
+}
+
+/// Mapa de diferencias (rojo = fuera de tolerancia, gris tenue = igual) y
+/// cantidad de píxeles que superan `TOLERANCE`
+fn compare(expected: &RgbImage, actual: &RgbImage) -> (RgbImage, usize) {
+    let mut bad = 0;
+    let diff = RgbImage::from_fn(actual.width(), actual.height(), |x, y| {
+        let (e, a) = (expected.get_pixel(x, y).0, actual.get_pixel(x, y).0);
+        let delta = (0..3).map(|c| e[c].abs_diff(a[c])).max().unwrap_or(0);
+        if delta > TOLERANCE {
+            bad += 1;
+            Rgb([255, 0, 0])
+        } else {
+            let l = ((e[0] as u32 + e[1] as u32 + e[2] as u32) / 12) as u8;
+            Rgb([l, l, l])
+        }
+    });
+    (diff, bad)
+}
+
+#[test]
+fn golden_maze1_spawn() {
+    check("maze1_spawn", "maze1.txt", 2.5, 2.5, 0.0);
+}
+
+#[test]
+fn golden_maze1_item_and_enemy() {
+    // Mira hacia el sur: el objeto '3' a media distancia y el enemigo al fondo
+    check("maze1_sprites", "maze1.txt", 9.5, 1.5, 82.0);
+}
+
+#[test]
+fn golden_maze2_corridor() {
+    check("maze2_corridor", "maze2.txt", 2.5, 4.5, 0.0);
+}
+
+#[test]
+fn golden_maze2_diagonal() {
+    check("maze2_diagonal", "maze2.txt", 20.5, 1.5, 135.0);
+}
+
+#[test]
+fn golden_maze3_spawn() {
+    check("maze3_spawn", "maze3.txt", 1.5, 1.5, 45.0);
+}
+
+#[test]
+fn golden_maze3_wall_closeup() {
+    // Pegado a la pared de colores del borde sur, frente a "BAB" (la 'C' no tiene textura)
+    check("maze3_wall", "maze3.txt", 15.5, 8.5, 90.0);
+}
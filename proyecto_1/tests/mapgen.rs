@@ -0,0 +1,17 @@
+//! Laberintos generados: siempre válidos y con todos los objetos alcanzables.
+
+use ej_wolfenstain::level::Level;
+use ej_wolfenstain::mapgen::{generate, GenOptions, MazeKind};
+use ej_wolfenstain::BLOCK_SIZE;
+
+#[test]
+fn large_generated_mazes_are_playable() {
+    for kind in [MazeKind::Backtracker, MazeKind::Rooms] {
+        let opts = GenOptions { width: 121, height: 91, items: 8, kind, seed: 1 };
+        let level = Level::from_grid("prueba", generate(&opts)).unwrap();
+        assert_eq!(level.total_items, 8);
+        let player = level.spawn_player(BLOCK_SIZE, std::f32::consts::FRAC_PI_2);
+        let report = level.reachability(player.radius, BLOCK_SIZE);
+        assert!(level.validate(&report).is_empty(), "{:?}", kind);
+    }
+}
@@ -0,0 +1,113 @@
+//! Grabación y reproducción de partidas.
+
+use ej_wolfenstain::framebuffer::Framebuffer;
+use ej_wolfenstain::game::{Game, LevelSource, Scene};
+use ej_wolfenstain::hud::load_font;
+use ej_wolfenstain::bindings::{Action, Actions};
+use ej_wolfenstain::platform::{Key, NullPlatform, Platform};
+use ej_wolfenstain::replay::Replay;
+use ej_wolfenstain::settings::Settings;
+use ej_wolfenstain::world::Outcome;
+
+#[test]
+fn recorded_run_of_level_1_still_wins() {
+    let replay = Replay::load("replays/nivel1.replay").unwrap();
+    let (world, outcome) = replay.run().unwrap();
+    assert_eq!(outcome, Outcome::Won);
+    assert_eq!(world.player.get_collected_items(), 3);
+}
+
+#[test]
+fn game_plays_back_to_victory() {
+    let replay = Replay::load("replays/nivel1.replay").unwrap();
+    let ticks = replay.ticks.len();
+    let mut game = Game::new(load_font("fonts/Arial.ttf"), 160, 120);
+    let mut platform = NullPlatform::new();
+    let mut fb = Framebuffer::new(160, 120);
+    game.play(replay, &mut platform).unwrap();
+    for _ in 0..ticks {
+        game.frame(1.0 / 60.0, &mut platform, &mut fb);
+    }
+    assert!(matches!(game.scene(), Scene::Victory));
+    assert!(!game.playback_finished());
+}
+
+#[test]
+fn short_replay_hands_control_back() {
+    let mut replay = Replay::load("replays/nivel1.replay").unwrap();
+    replay.ticks.truncate(10);
+    let mut game = Game::new(load_font("fonts/Arial.ttf"), 160, 120);
+    let mut platform = NullPlatform::new();
+    let mut fb = Framebuffer::new(160, 120);
+    game.play(replay, &mut platform).unwrap();
+    for _ in 0..20 {
+        game.frame(1.0 / 60.0, &mut platform, &mut fb);
+    }
+    assert!(game.playback_finished());
+    assert_eq!(game.world().and_then(|w| w.message()), Some("Fin de la repetición"));
+}
+
+#[test]
+fn text_format_round_trip() {
+    let mut replay = Replay::new(LevelSource::Random(42));
+    let mut actions = Actions::default();
+    actions.hold(Action::MoveForward);
+    replay.ticks.extend([actions; 30]);
+    actions.press(Action::Fire);
+    actions.turn = -0.0123;
+    replay.ticks.push(actions);
+    replay.ticks.push(Actions::default());
+
+    let text = replay.to_text();
+    assert_eq!(text.lines().count(), 2 + 3); // cabecera, semilla y tres rachas
+    assert_eq!(Replay::parse(&text, "prueba").unwrap(), replay);
+    assert!(Replay::parse("replay 2\nlevel x\n3 zz 0 0\n", "prueba").is_err());
+
+    // Con velocidades propias se agrega una línea a la cabecera
+    replay.gameplay.move_speed = 300.0;
+    let text = replay.to_text();
+    assert_eq!(text.lines().nth(2), Some("gameplay 300 1.8 3"));
+    assert_eq!(Replay::parse(&text, "prueba").unwrap(), replay);
+}
+
+#[test]
+fn game_recording_replays_headless() {
+    let (w, h) = (160, 120);
+    let mut game = Game::new(load_font("fonts/Arial.ttf"), w, h);
+    let mut platform = NullPlatform::new();
+    let mut fb = Framebuffer::new(w, h);
+    let mut settings = Settings::default();
+    settings.controls.mouse_look = true;
+    game.set_settings(settings, None, &mut platform);
+    game.record();
+    let mut frame = |game: &mut Game, platform: &mut NullPlatform, dt: f32| {
+        game.frame(dt, platform, &mut fb);
+        platform.present(&fb);
+    };
+
+    platform.press(Key::Enter);
+    frame(&mut game, &mut platform, 1.0 / 60.0);
+    platform.press(Key::Key1);
+    frame(&mut game, &mut platform, 1.0 / 60.0);
+    // Cuadros irregulares, con mouse y una pausa en el medio
+    platform.hold(Key::W);
+    platform.hold(Key::D);
+    platform.mouse = Some(80.0);
+    for n in 0..50 {
+        frame(&mut game, &mut platform, [0.01, 0.03, 0.007][n % 3]);
+    }
+    platform.press(Key::Escape);
+    frame(&mut game, &mut platform, 1.0 / 60.0);
+    platform.press(Key::Escape);
+    frame(&mut game, &mut platform, 1.0 / 60.0);
+    platform.release(Key::D);
+    for n in 0..20 {
+        platform.mouse = Some(80.0 - n as f32 * 3.0);
+        frame(&mut game, &mut platform, 1.0 / 45.0);
+    }
+
+    let live = game.world().map(|w| (w.player.pos.x, w.player.pos.y, w.player.a)).unwrap();
+    let replay = Replay::parse(&game.recording().unwrap().to_text(), "grabación").unwrap();
+    let (world, _) = replay.run().unwrap();
+    assert_eq!((world.player.pos.x, world.player.pos.y, world.player.a), live);
+}
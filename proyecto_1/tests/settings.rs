@@ -0,0 +1,107 @@
+//! Archivo de ajustes: valores por defecto, validación y guardado desde el juego.
+
+use ej_wolfenstain::audio::Channel;
+use ej_wolfenstain::framebuffer::Framebuffer;
+use ej_wolfenstain::game::Game;
+use ej_wolfenstain::hud::load_font;
+use ej_wolfenstain::platform::{Key, NullPlatform, Platform};
+use ej_wolfenstain::settings::Settings;
+
+#[test]
+fn missing_fields_use_defaults_and_bad_values_are_fixed() {
+    let text = r#"
+        [video]
+        width = 100
+        fov = 75
+
+        [audio]
+        music = 2.5
+
+        [controls.keys]
+        fire = ["Ctrl"]
+    "#;
+    let mut settings = Settings::parse(text, "prueba").unwrap();
+    assert_eq!(settings.video.height, 480);
+    assert_eq!(settings.gameplay, Settings::default().gameplay);
+
+    let warnings = settings.validate();
+    assert_eq!(warnings.len(), 2, "{:?}", warnings);
+    assert_eq!(settings.video.width, 640);
+    assert_eq!(settings.video.fov, 75.0);
+    assert_eq!(settings.audio.music, 1.0);
+    assert_eq!(settings.bindings().keys(ej_wolfenstain::bindings::Action::Fire), [Key::LeftCtrl]);
+
+    let mut unknown = Settings::parse("[controls.keys]\nfire = [\"Z\"]", "prueba").unwrap();
+    assert_eq!(unknown.validate().len(), 1);
+    assert_eq!(unknown.controls, Settings::default().controls);
+}
+
+#[test]
+fn settings_screen_writes_the_file() {
+    let dir = std::env::temp_dir().join(format!("ej_wolfenstain_ajustes_{}", std::process::id()));
+    let path = dir.join("settings.toml");
+    let _ = std::fs::remove_dir_all(&dir);
+
+    let mut platform = NullPlatform::new();
+    let mut fb = Framebuffer::new(160, 120);
+    let mut game = Game::new(load_font("fonts/Arial.ttf"), 160, 120);
+    let mut settings = Settings::default();
+    settings.audio.effects = 0.5;
+    game.set_settings(settings, Some(path.clone()), &mut platform);
+    assert_eq!(platform.volume(Channel::Effects), 0.5);
+
+    let mut tap = |key| {
+        platform.press(key);
+        game.frame(1.0 / 60.0, &mut platform, &mut fb);
+        platform.present(&fb);
+    };
+    tap(Key::Enter);
+    tap(Key::Key5);
+    tap(Key::Left); // volumen general
+    tap(Key::Up); // resolución, la última fila
+    tap(Key::Right);
+    assert!(!path.exists(), "solo se guarda al salir de la pantalla");
+    tap(Key::Escape);
+
+    let saved = Settings::load(&path).unwrap();
+    assert_eq!(saved.audio.master, 0.9);
+    assert_eq!(saved.audio.effects, 0.5);
+    assert_eq!((saved.video.width, saved.video.height), (800, 600));
+    assert!(std::fs::read_to_string(&path).unwrap().contains("master = 0.9\n"));
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn command_line_video_is_not_saved() {
+    let dir = std::env::temp_dir().join(format!("ej_wolfenstain_sesion_{}", std::process::id()));
+    let path = dir.join("settings.toml");
+    let _ = std::fs::remove_dir_all(&dir);
+
+    let mut platform = NullPlatform::new();
+    let mut fb = Framebuffer::new(160, 120);
+    let mut game = Game::new(load_font("fonts/Arial.ttf"), 160, 120);
+    game.set_settings(Settings::default(), Some(path.clone()), &mut platform);
+    let mut video = game.settings().video;
+    (video.width, video.fov) = (1024, 120.0);
+    game.set_video(video);
+
+    for key in [Key::Enter, Key::Key5, Key::Left, Key::Escape] {
+        platform.press(key);
+        game.frame(1.0 / 60.0, &mut platform, &mut fb);
+        platform.present(&fb);
+    }
+    assert_eq!(game.settings().video.fov, 120.0);
+    let saved = Settings::load(&path).unwrap();
+    assert_eq!(saved.audio.master, 0.9);
+    assert_eq!(saved.video, Settings::default().video);
+
+    // Un paso de campo de visión se guarda desde el valor del archivo, no desde `--fov`
+    for key in [Key::Key5, Key::Down, Key::Down, Key::Down, Key::Right, Key::Escape] {
+        platform.press(key);
+        game.frame(1.0 / 60.0, &mut platform, &mut fb);
+        platform.present(&fb);
+    }
+    assert_eq!(game.settings().video.fov, 125.0);
+    assert_eq!(Settings::load(&path).unwrap().video.fov, 95.0);
+    std::fs::remove_dir_all(&dir).unwrap();
+}
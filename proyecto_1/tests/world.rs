@@ -0,0 +1,151 @@
+//! Reglas del juego sin ventana ni audio, a través de `NullPlatform`.
+
+use ej_wolfenstain::audio::SoundId;
+use ej_wolfenstain::bindings::{Action, Actions};
+use ej_wolfenstain::level::Level;
+use ej_wolfenstain::platform::NullPlatform;
+use ej_wolfenstain::world::{Outcome, World};
+use ej_wolfenstain::BLOCK_SIZE;
+
+const DT: f32 = 1.0 / 60.0;
+
+/// Mundo de maze1 con el jugador en el centro de la celda (x, y) mirando a `degrees`
+fn world_at(x: f32, y: f32, degrees: f32) -> World {
+    let mut world = World::new(Level::load("maze1.txt").unwrap());
+    let bs = BLOCK_SIZE as f32;
+    world.player.pos.x = x * bs;
+    world.player.pos.y = y * bs;
+    world.player.a = degrees.to_radians();
+    world
+}
+
+/// Un paso de simulación con una sola acción (o ninguna)
+fn step(world: &mut World, platform: &mut NullPlatform, dt: f32, action: Option<(Action, bool)>) -> Outcome {
+    let mut actions = Actions::default();
+    match action {
+        Some((action, true)) => actions.press(action),
+        Some((action, false)) => actions.hold(action),
+        None => {}
+    }
+    world.update(dt, &actions, platform)
+}
+
+#[test]
+fn use_key_picks_up_item_in_front() {
+    // El objeto '1' está en la celda (4, 2)
+    let mut world = world_at(3.5, 2.5, 0.0);
+    let mut platform = NullPlatform::new();
+    step(&mut world, &mut platform, DT, Some((Action::Use, true)));
+
+    assert_eq!(world.player.get_collected_items(), 1);
+    assert_eq!(world.maze[2][4], ' ');
+    assert_eq!(platform.sounds, [SoundId::Pickup]);
+    assert_eq!(world.message(), Some("Objeto 1/3"));
+}
+
+#[test]
+fn armour_and_ammo_pickups() {
+    let grid = ["####", "#Pa#", "#m #", "####"].iter().map(|row| row.chars().collect()).collect();
+    let level = Level::from_grid("prueba", grid).unwrap();
+    assert_eq!(level.total_items, 0);
+    let mut world = World::new(level);
+    let mut platform = NullPlatform::new();
+    step(&mut world, &mut platform, DT, Some((Action::Use, true)));
+    assert_eq!(world.player.armour, 50);
+    world.player.a = 90f32.to_radians();
+    step(&mut world, &mut platform, DT, Some((Action::Use, true)));
+    assert_eq!(world.player.ammo, 40);
+    assert_eq!(world.message(), Some("Munición 40"));
+
+    // La armadura absorbe la mitad del golpe
+    world.player.take_damage(20);
+    assert_eq!((world.player.health, world.player.armour), (90, 40));
+}
+
+#[test]
+fn locked_door_asks_for_its_key() {
+    let grid = ["####", "#PR#", "####"].iter().map(|row| row.chars().collect()).collect();
+    let mut world = World::new(Level::from_grid("prueba", grid).unwrap());
+    let mut platform = NullPlatform::new();
+    step(&mut world, &mut platform, DT, Some((Action::Use, true)));
+    assert_eq!(world.message(), Some("Necesitas la llave roja"));
+
+    // El aviso se borra solo al rato
+    for _ in 0..5 {
+        step(&mut world, &mut platform, 1.0, None);
+    }
+    assert_eq!(world.message(), None);
+}
+
+#[test]
+fn walls_stop_the_player() {
+    let mut world = world_at(1.5, 1.5, 180.0);
+    let mut platform = NullPlatform::new();
+    for _ in 0..60 {
+        step(&mut world, &mut platform, DT, Some((Action::MoveForward, false)));
+    }
+    // No atraviesa la pared de la columna 0
+    assert!(world.player.pos.x - world.player.radius >= BLOCK_SIZE as f32);
+}
+
+#[test]
+fn shooting_kills_enemy() {
+    // El enemigo 'e' está en (10, 8); el jugador lo mira desde arriba
+    let mut world = world_at(10.5, 5.5, 90.0);
+    let mut platform = NullPlatform::new();
+    assert_eq!(world.enemies.len(), 1);
+    for _ in 0..2 {
+        step(&mut world, &mut platform, 0.4, Some((Action::Fire, true)));
+    }
+    assert!(world.enemies.is_empty());
+    assert_eq!(world.player.ammo, 28);
+    assert_eq!(platform.sounds.iter().filter(|&&s| s == SoundId::Shot).count(), 2);
+}
+
+#[test]
+fn enemy_attacks_until_game_over() {
+    let mut world = world_at(10.5, 7.5, 0.0);
+    let mut platform = NullPlatform::new();
+    let outcome = (0..60 * 30)
+        .map(|_| step(&mut world, &mut platform, DT, None))
+        .find(|&o| o != Outcome::Playing);
+    assert_eq!(outcome, Some(Outcome::Lost));
+    assert!(world.player.is_dead());
+}
+
+#[test]
+fn strafe_moves_sideways_without_turning() {
+    // Mirando al este, el paso a la derecha va hacia el sur (y crece)
+    let mut world = world_at(2.5, 1.5, 0.0);
+    let mut platform = NullPlatform::new();
+    let start = (world.player.pos.x, world.player.pos.y);
+    for _ in 0..10 {
+        step(&mut world, &mut platform, DT, Some((Action::StrafeRight, false)));
+    }
+    assert_eq!(world.player.pos.x, start.0);
+    assert!(world.player.pos.y > start.1);
+    assert_eq!(world.player.a, 0.0);
+}
+
+#[test]
+fn enemy_alert_comes_from_its_side() {
+    // Mirando al este con el enemigo de (10, 8) al sur: se oye a la derecha
+    let mut world = world_at(10.5, 6.5, 0.0);
+    let mut platform = NullPlatform::new();
+    for _ in 0..30 {
+        step(&mut world, &mut platform, DT, None);
+    }
+    let (_, alert) = platform.spatial.iter().find(|(id, _)| *id == SoundId::EnemyAlert).unwrap();
+    assert!(alert.pan > 0.8 && alert.volume > 0.5, "{:?}", alert);
+    // Y el objetivo más cercano zumba mientras queden objetos
+    assert!(platform.loops.contains_key(&SoundId::Hum));
+}
+
+#[test]
+fn benchmark_plays_every_frame() {
+    let font = ej_wolfenstain::hud::load_font("fonts/Arial.ttf");
+    let level = Level::load("maze1.txt").unwrap();
+    let report = ej_wolfenstain::headless::benchmark(level, 5, 80, 60, &font);
+    assert_eq!(report.frames, 5);
+    assert!(report.slowest <= report.total && report.average() <= report.slowest);
+}